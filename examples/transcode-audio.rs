@@ -13,28 +13,31 @@ fn filter(
     encoder: &codec::encoder::Audio,
 ) -> Result<filter::Graph, ffmpeg::Error> {
     let mut filter = filter::Graph::new();
+    let pads = filter.parse_open(spec)?;
 
-    let channel_layout = decoder.ch_layout().description();
-
-    let args = format!(
-        "time_base={}:sample_rate={}:sample_fmt={}:channel_layout={channel_layout}",
-        decoder.time_base(),
-        decoder.rate(),
-        decoder.format().name()
-    );
+    // name the endpoints `in` and `out` whether or not the spec labels them
+    let (Some(input), Some(output)) = (pads.inputs.first(), pads.outputs.first()) else {
+        return Err(ffmpeg::Error::InvalidData);
+    };
+    let input = filter::OpenPad {
+        label: Some("in".to_owned()),
+        ..input.clone()
+    };
+    let output = filter::OpenPad {
+        label: Some("out".to_owned()),
+        ..output.clone()
+    };
 
-    filter.add(&filter::find("abuffer").unwrap(), "in", &args)?;
-    filter.add(&filter::find("abuffersink").unwrap(), "out", "")?;
+    filter.add_source(&input, &Parameters::from(decoder), decoder.time_base())?;
 
     {
-        let mut out = filter.get("out").unwrap();
+        let mut out = filter.add_sink(&output)?;
 
         out.set_sample_format(encoder.format());
         out.set_ch_layout(encoder.ch_layout());
         out.set_sample_rate(encoder.rate());
     }
 
-    filter.output("in", 0)?.input("out", 0)?.parse(spec)?;
     filter.validate()?;
 
     println!("{}", filter.dump());
@@ -45,9 +48,7 @@ fn filter(
             .contains(ffmpeg::codec::capabilities::Capabilities::VARIABLE_FRAME_SIZE)
         {
             filter
-                .get("out")
-                .unwrap()
-                .sink()
+                .buffer_sink("out")?
                 .set_frame_size(encoder.frame_size());
         }
     }
@@ -170,20 +171,19 @@ impl Transcoder {
     }
 
     fn add_frame_to_filter(&mut self, frame: &ffmpeg::Frame) {
-        self.filter.get("in").unwrap().source().add(frame).unwrap();
+        self.filter.buffer_source("in").unwrap().add(frame).unwrap();
     }
 
     fn flush_filter(&mut self) {
-        self.filter.get("in").unwrap().source().flush().unwrap();
+        self.filter.buffer_source("in").unwrap().flush().unwrap();
     }
 
     fn get_and_process_filtered_frames(&mut self, octx: &mut format::context::Output) {
         let mut filtered = frame::Audio::empty();
         while self
             .filter
-            .get("out")
+            .buffer_sink("out")
             .unwrap()
-            .sink()
             .frame(&mut filtered)
             .is_ok()
         {
@@ -216,6 +216,7 @@ static SWRESAMPLE_FEATURES: &[AVFeature] = &[];
 struct AVHeader {
     name: &'static str,
     min_ver: Option<u64>,
+    min_minor_ver: u64,
     max_ver: Option<u64>,
 }
 
@@ -224,6 +225,7 @@ impl AVHeader {
         Self {
             name,
             min_ver: None,
+            min_minor_ver: 0,
             max_ver: None,
         }
     }
@@ -233,6 +235,13 @@ impl AVHeader {
         self
     }
 
+    /// Minimum minor version within the `min_ver` major version, for headers
+    /// added during a release cycle.
+    const fn min_minor_ver(mut self, ver: u64) -> Self {
+        self.min_minor_ver = ver;
+        self
+    }
+
     const fn max_ver(mut self, ver: u64) -> Self {
         self.max_ver = Some(ver);
         self
@@ -252,15 +261,19 @@ static AVUTIL_HEADERS: &[AVHeader] = &[
     AVHeader::new("channel_layout.h"),
     AVHeader::new("cpu.h"),
     AVHeader::new("crc.h"),
+    AVHeader::new("detection_bbox.h"),
     AVHeader::new("dict.h"),
     AVHeader::new("display.h"),
+    AVHeader::new("dovi_meta.h"),
     AVHeader::new("downmix_info.h"),
     AVHeader::new("error.h"),
     AVHeader::new("eval.h"),
     AVHeader::new("fifo.h"),
     AVHeader::new("file.h"),
+    AVHeader::new("film_grain_params.h"),
     AVHeader::new("frame.h"),
     AVHeader::new("hash.h"),
+    AVHeader::new("hdr_dynamic_metadata.h"),
     AVHeader::new("hmac.h"),
     AVHeader::new("hwcontext.h"),
     AVHeader::new("hwcontext_drm.h"),
@@ -269,6 +282,7 @@ static AVUTIL_HEADERS: &[AVHeader] = &[
     AVHeader::new("log.h"),
     AVHeader::new("lzo.h"),
     AVHeader::new("macros.h"),
+    AVHeader::new("mastering_display_metadata.h"),
     AVHeader::new("mathematics.h"),
     AVHeader::new("md5.h"),
     AVHeader::new("mem.h"),
@@ -285,6 +299,7 @@ static AVUTIL_HEADERS: &[AVHeader] = &[
     AVHeader::new("samplefmt.h"),
     AVHeader::new("sha.h"),
     AVHeader::new("sha512.h"),
+    AVHeader::new("spherical.h"),
     AVHeader::new("stereo3d.h"),
     AVHeader::new("avstring.h"),
     AVHeader::new("threadmessage.h"),
@@ -293,13 +308,15 @@ static AVUTIL_HEADERS: &[AVHeader] = &[
     AVHeader::new("twofish.h"),
     AVHeader::new("tx.h").min_ver(60), // post-8.0
     AVHeader::new("avutil.h"),
+    AVHeader::new("video_enc_params.h"),
     AVHeader::new("xtea.h"),
 ];
 static AVCODEC_HEADERS: &[AVHeader] = &[
     AVHeader::new("avcodec.h"),
     AVHeader::new("bsf.h"),
     AVHeader::new("dv_profile.h"),
-    AVHeader::new("avfft.h").max_ver(61), // pre-8.0
+    AVHeader::new("exif.h").min_ver(62).min_minor_ver(28), // post-8.1
+    AVHeader::new("avfft.h").max_ver(61),                  // pre-8.0
     AVHeader::new("vorbis_parser.h"),
 ];
 static AVFORMAT_HEADERS: &[AVHeader] = &[AVHeader::new("avformat.h"), AVHeader::new("avio.h")];
@@ -344,20 +361,30 @@ impl Default for Callbacks {
                 ("enum AVFrameSideDataType", "AV_FRAME_DATA_"),
                 ("enum AVActiveFormatDescription", "AV_AFD_"),
                 ("enum AVSideDataProps", "AV_SIDE_DATA_PROP_"),
+                ("enum AVDOVIMappingMethod", "AV_DOVI_MAPPING_"),
+                ("enum AVDOVINLQMethod", "AV_DOVI_NLQ_"),
+                ("enum AVDOVICompression", "AV_DOVI_COMPRESSION_"),
                 ("enum AVDownmixType", "AV_DOWNMIX_TYPE_"),
+                ("enum AVFilmGrainParamsType", "AV_FILM_GRAIN_PARAMS_"),
                 ("enum AVHMACType", "AV_HMAC_"),
+                (
+                    "enum AVHDRPlusOverlapProcessOption",
+                    "AV_HDR_PLUS_OVERLAP_PROCESS_",
+                ),
                 ("enum AVHWDeviceType", "AV_HWDEVICE_TYPE_"),
                 (
                     "enum AVHWFrameTransferDirection",
                     "AV_HWFRAME_TRANSFER_DIRECTION_",
                 ),
                 ("enum AVOptionType", "AV_OPT_TYPE_"),
+                ("enum AVSphericalProjection", "AV_SPHERICAL_"),
                 ("enum AVStereo3DType", "AV_STEREO3D_"),
                 ("enum AVStereo3DPrimaryEye", "AV_PRIMARY_EYE_"),
                 ("enum AVThreadMessageFlags", "AV_THREAD_MESSAGE_"),
                 ("enum AVTimecodeFlag", "AV_TIMECODE_FLAG_"),
                 ("enum AVTXType", "AV_TX_"),
                 ("enum AVTXFlags", "AV_TX_"),
+                ("enum AVVideoEncParamsType", "AV_VIDEO_ENC_PARAMS_"),
                 ("enum AVCodecID", "AV_CODEC_ID_"),
                 ("enum AVClassStateFlags", "AV_CLASS_STATE_"),
                 ("enum AVStereo3DView", "AV_STEREO3D_VIEW_"),
@@ -392,6 +419,8 @@ impl Default for Callbacks {
                 // 8.1
                 ("enum AVAlphaMode", "AVALPHA_MODE_"),
                 ("enum AVFormatCommandID", "AVFORMAT_COMMAND_"),
+                ("enum AVExifHeaderMode", "AV_EXIF_"),
+                ("enum AVTiffDataType", "AV_TIFF_"),
             ]),
         }
     }
@@ -838,8 +867,17 @@ fn main() {
 
 fn add_include(s: &mut String, lib: &Library, header: &AVHeader) -> std::fmt::Result {
     let ver_name = format!("LIB{}_VERSION_MAJOR", lib.name.to_uppercase());
+    let minor_name = format!("LIB{}_VERSION_MINOR", lib.name.to_uppercase());
 
     let end_if = match (header.min_ver, header.max_ver) {
+        (Some(from), None) if header.min_minor_ver > 0 => {
+            writeln!(
+                s,
+                "#if {ver_name} > {from} || ({ver_name} == {from} && {minor_name} >= {})",
+                header.min_minor_ver
+            )?;
+            true
+        }
         (Some(from), Some(to)) => {
             writeln!(s, "#if {ver_name} >= {from} && {ver_name} <= {to}")?;
             true
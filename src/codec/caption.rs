@@ -0,0 +1,225 @@
+//! Closed caption extraction from `A53CC` frame side data.
+//!
+//! CEA-608 captions embedded in the video frames are decoded with FFmpeg's `cc_dec`
+//! decoder and returned as timed text cues, which can be written as SRT or WebVTT.
+
+use std::io;
+
+use super::{decoder, Context, Id};
+use crate::ffi::*;
+use crate::frame::side_data;
+use crate::{Error, Frame, Packet, Rational, Subtitle};
+
+/// A caption with start and end times in milliseconds.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Cue {
+    pub start: i64,
+    pub end: i64,
+    pub text: String,
+}
+
+pub struct Extractor {
+    decoder: decoder::Subtitle,
+    subtitle: Subtitle,
+}
+
+impl Extractor {
+    /// Creates an extractor for frames with timestamps in `time_base`.
+    pub fn new(time_base: Rational) -> Result<Self, Error> {
+        let codec = decoder::find(Id::EIA_608).ok_or(Error::DecoderNotFound)?;
+        let mut context = Context::new_with_codec(codec);
+
+        unsafe {
+            (*context.as_mut_ptr()).pkt_timebase = time_base.into();
+        }
+
+        Ok(Extractor {
+            decoder: context.decoder().open_as(codec)?.subtitle()?,
+            subtitle: Subtitle::new(),
+        })
+    }
+
+    /// Feeds the `A53CC` side data of `frame`, returning the cues completed by it.
+    pub fn push(&mut self, frame: &Frame) -> Result<Vec<Cue>, Error> {
+        match frame.side_data(side_data::Type::A53CC) {
+            Some(side_data) => self.push_data(side_data.data(), frame.pts().or(frame.timestamp())),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Feeds raw A53 `cc_data` triplets presented at `pts`, returning the cues completed by it.
+    pub fn push_data(&mut self, data: &[u8], pts: Option<i64>) -> Result<Vec<Cue>, Error> {
+        let mut packet = Packet::copy(data);
+        packet.set_pts(pts);
+
+        if !self.decoder.decode(&packet, &mut self.subtitle)? {
+            return Ok(Vec::new());
+        }
+
+        // subtitle pts is in AV_TIME_BASE, display times are relative in milliseconds
+        let base = self.subtitle.pts().unwrap_or(0) / 1000;
+        let start = base + i64::from(self.subtitle.start());
+        let end = base + i64::from(self.subtitle.end());
+
+        let cues = self
+            .subtitle
+            .rects()
+            .filter_map(|rect| {
+                rect.ass()
+                    .map(ass_to_text)
+                    .or_else(|| rect.text().map(str::to_owned))
+            })
+            .filter(|text| !text.trim().is_empty())
+            .map(|text| Cue { start, end, text })
+            .collect();
+
+        unsafe {
+            avsubtitle_free(self.subtitle.as_mut_ptr());
+        }
+
+        Ok(cues)
+    }
+}
+
+impl Drop for Extractor {
+    fn drop(&mut self) {
+        unsafe {
+            avsubtitle_free(self.subtitle.as_mut_ptr());
+        }
+    }
+}
+
+/// Extracts the plain text of an ASS dialogue event, dropping override tags.
+fn ass_to_text(ass: &str) -> String {
+    // ReadOrder, Layer, Style, Name, MarginL, MarginR, MarginV, Effect, Text
+    let text = ass.splitn(9, ',').nth(8).unwrap_or(ass);
+
+    let mut plain = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => {
+                for c in chars.by_ref() {
+                    if c == '}' {
+                        break;
+                    }
+                }
+            }
+            '\\' => match chars.peek() {
+                Some('N') | Some('n') => {
+                    chars.next();
+                    plain.push('\n');
+                }
+                Some('h') => {
+                    chars.next();
+                    plain.push(' ');
+                }
+                _ => plain.push(c),
+            },
+            _ => plain.push(c),
+        }
+    }
+
+    plain.trim_end().to_owned()
+}
+
+fn timestamp(ms: i64, separator: char) -> String {
+    let ms = ms.max(0);
+
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        ms / 3_600_000,
+        ms / 60_000 % 60,
+        ms / 1000 % 60,
+        separator,
+        ms % 1000
+    )
+}
+
+/// Writes `cues` in SubRip (SRT) format.
+pub fn write_srt<W: io::Write>(mut out: W, cues: &[Cue]) -> io::Result<()> {
+    for (index, cue) in cues.iter().enumerate() {
+        writeln!(out, "{}", index + 1)?;
+        writeln!(
+            out,
+            "{} --> {}",
+            timestamp(cue.start, ','),
+            timestamp(cue.end, ',')
+        )?;
+        writeln!(out, "{}\n", cue.text)?;
+    }
+
+    Ok(())
+}
+
+/// Writes `cues` in WebVTT format.
+pub fn write_webvtt<W: io::Write>(mut out: W, cues: &[Cue]) -> io::Result<()> {
+    writeln!(out, "WEBVTT\n")?;
+
+    for cue in cues {
+        writeln!(
+            out,
+            "{} --> {}",
+            timestamp(cue.start, '.'),
+            timestamp(cue.end, '.')
+        )?;
+        writeln!(out, "{}\n", cue.text)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cues() -> Vec<Cue> {
+        vec![
+            Cue {
+                start: 1000,
+                end: 2500,
+                text: "HELLO".to_owned(),
+            },
+            Cue {
+                start: 3_723_004,
+                end: 3_725_000,
+                text: "TWO\nLINES".to_owned(),
+            },
+        ]
+    }
+
+    #[test]
+    fn ass_text() {
+        assert_eq!(
+            ass_to_text(r"0,0,Default,,0,0,0,,{\an7}{\pos(115,228)}HELLO,\NWORLD"),
+            "HELLO,\nWORLD"
+        );
+        assert_eq!(ass_to_text(r"0,0,Default,,0,0,0,,A\hB"), "A B");
+    }
+
+    #[test]
+    fn srt() {
+        let mut out = Vec::new();
+        write_srt(&mut out, &cues()).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "1\n00:00:01,000 --> 00:00:02,500\nHELLO\n\n\
+             2\n01:02:03,004 --> 01:02:05,000\nTWO\nLINES\n\n"
+        );
+    }
+
+    #[test]
+    fn webvtt() {
+        let mut out = Vec::new();
+        write_webvtt(&mut out, &cues()).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "WEBVTT\n\n\
+             00:00:01.000 --> 00:00:02.500\nHELLO\n\n\
+             01:02:03.004 --> 01:02:05.000\nTWO\nLINES\n\n"
+        );
+    }
+}
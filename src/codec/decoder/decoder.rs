@@ -1,7 +1,7 @@
 use std::ops::{Deref, DerefMut};
 use std::ptr;
 
-use super::{Audio, Check, Conceal, Opened, Subtitle, Video};
+use super::{Audio, Check, Conceal, ExportSideData, Opened, Subtitle, Video};
 use crate::codec::{traits, Context};
 use crate::ffi::*;
 use crate::{AsMutPtr, Discard, Error, Rational};
@@ -93,6 +93,14 @@ impl Decoder {
         }
     }
 
+    /// Selects the optional side data the decoder attaches to its frames, e.g.
+    /// `MOTION_VECTORS` (equivalent to `flags2 +export_mvs`).
+    pub fn export_side_data(&mut self, value: ExportSideData) {
+        unsafe {
+            (*self.as_mut_ptr()).export_side_data = value.bits();
+        }
+    }
+
     pub fn skip_loop_filter(&mut self, value: Discard) {
         unsafe {
             (*self.as_mut_ptr()).skip_loop_filter = value.into();
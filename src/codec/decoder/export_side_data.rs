@@ -0,0 +1,45 @@
+use crate::ffi::*;
+use libc::c_int;
+
+bitflags::bitflags! {
+    pub struct ExportSideData: c_int {
+        const MOTION_VECTORS   = AV_CODEC_EXPORT_DATA_MVS;
+        const PRFT             = AV_CODEC_EXPORT_DATA_PRFT;
+        const VIDEO_ENC_PARAMS = AV_CODEC_EXPORT_DATA_VIDEO_ENC_PARAMS;
+        const FILM_GRAIN       = AV_CODEC_EXPORT_DATA_FILM_GRAIN;
+        #[cfg(feature = "ffmpeg_7_1")]
+        const ENHANCEMENTS     = AV_CODEC_EXPORT_DATA_ENHANCEMENTS;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::{decoder, Context, Id};
+
+    #[test]
+    fn flags() {
+        let mut decoder = Context::new().decoder();
+        assert_eq!(unsafe { (*decoder.as_ptr()).export_side_data }, 0);
+
+        let flags = ExportSideData::MOTION_VECTORS | ExportSideData::FILM_GRAIN;
+        decoder.export_side_data(flags);
+        assert_eq!(
+            unsafe { (*decoder.as_ptr()).export_side_data },
+            flags.bits()
+        );
+        assert_eq!(
+            flags.bits(),
+            AV_CODEC_EXPORT_DATA_MVS | AV_CODEC_EXPORT_DATA_FILM_GRAIN
+        );
+
+        // the flags survive opening the decoder
+        let codec = decoder::find(Id::MPEG2VIDEO).expect("MPEG-2 decoder must be available");
+        let opened = decoder.open_as(codec).unwrap();
+        assert_eq!(unsafe { (*opened.as_ptr()).export_side_data }, flags.bits());
+
+        let mut decoder = Context::new().decoder();
+        decoder.export_side_data(ExportSideData::empty());
+        assert_eq!(unsafe { (*decoder.as_ptr()).export_side_data }, 0);
+    }
+}
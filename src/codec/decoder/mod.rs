@@ -18,6 +18,9 @@ pub use self::conceal::Conceal;
 pub mod check;
 pub use self::check::Check;
 
+pub mod export_side_data;
+pub use self::export_side_data::ExportSideData;
+
 pub mod opened;
 pub use self::opened::Opened;
 
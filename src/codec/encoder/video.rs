@@ -341,6 +341,32 @@ impl Video {
     pub fn color_range(&self) -> color::Range {
         unsafe { (*self.as_ptr()).color_range.into() }
     }
+
+    /// Allocates a zeroed entry of `size` bytes in the stream side data the
+    /// encoder exports, replacing any existing entry of that type.
+    #[cfg(feature = "ffmpeg_7_0")]
+    pub(crate) unsafe fn new_decoded_side_data(
+        &mut self,
+        kind: crate::frame::side_data::Type,
+        size: usize,
+    ) -> Result<*mut u8, Error> {
+        let ctx = self.as_mut_ptr();
+        let side_data = av_frame_side_data_new(
+            &mut (*ctx).decoded_side_data,
+            &mut (*ctx).nb_decoded_side_data,
+            kind.into(),
+            size,
+            AV_FRAME_SIDE_DATA_FLAG_UNIQUE as _,
+        );
+
+        if side_data.is_null() {
+            return Err(Error::Other {
+                errno: libc::ENOMEM,
+            });
+        }
+
+        Ok((*side_data).data)
+    }
 }
 
 impl Deref for Video {
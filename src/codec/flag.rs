@@ -22,5 +22,8 @@ bitflags::bitflags! {
         const LOOP_FILTER     = AV_CODEC_FLAG_LOOP_FILTER;
         const INTERLACED_ME   = AV_CODEC_FLAG_INTERLACED_ME;
         const CLOSED_GOP      = AV_CODEC_FLAG_CLOSED_GOP;
+        /// Pass the `opaque_ref` of each input on to the outputs produced from it.
+        #[cfg(feature = "ffmpeg_6_0")]
+        const COPY_OPAQUE     = AV_CODEC_FLAG_COPY_OPAQUE;
     }
 }
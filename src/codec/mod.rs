@@ -8,6 +8,8 @@ pub mod packet;
 
 pub mod subtitle;
 
+pub mod caption;
+
 pub mod descriptor;
 pub use self::descriptor::CodecDescriptor;
 
@@ -1,8 +1,9 @@
 use std::marker::PhantomData;
 use std::mem;
+use std::ptr;
 use std::slice;
 
-use super::{Borrow, Flags, Mut, Ref, SideData};
+use super::{side_data, Borrow, Flags, Mut, Ref, SideData};
 use crate::ffi::*;
 use crate::{format, Error, Rational};
 use libc::c_int;
@@ -173,6 +174,64 @@ impl Packet {
         SideDataIter::new(&self.0)
     }
 
+    /// Adds a copy of `data` as side data, replacing any existing entry of the
+    /// same type.
+    pub fn add_side_data(&mut self, kind: side_data::Type, data: &[u8]) -> Result<(), Error> {
+        unsafe {
+            let ptr = av_packet_new_side_data(&mut self.0, kind.into(), data.len() as _);
+
+            if ptr.is_null() {
+                return Err(Error::Other {
+                    errno: libc::ENOMEM,
+                });
+            }
+
+            ptr::copy_nonoverlapping(data.as_ptr(), ptr, data.len());
+        }
+
+        Ok(())
+    }
+
+    pub fn side_data_mut(&mut self, kind: side_data::Type) -> Option<&mut [u8]> {
+        unsafe {
+            let mut size = 0;
+            let ptr = av_packet_get_side_data(&self.0, kind.into(), &mut size);
+
+            if ptr.is_null() {
+                None
+            } else {
+                Some(slice::from_raw_parts_mut(ptr, size as usize))
+            }
+        }
+    }
+
+    pub fn remove_side_data(&mut self, kind: side_data::Type) {
+        let kind = AVPacketSideDataType::from(kind);
+
+        unsafe {
+            let side_data = self.0.side_data;
+            let elems = &mut self.0.side_data_elems;
+
+            for i in (0..*elems as usize).rev() {
+                let entry = side_data.add(i);
+
+                if (*entry).type_ == kind {
+                    av_freep(&mut (*entry).data as *mut *mut u8 as *mut _);
+
+                    // like av_packet_side_data_remove(), move the last entry into the gap
+                    *elems -= 1;
+                    *entry = *side_data.add(*elems as usize);
+                }
+            }
+        }
+    }
+
+    pub fn clear_side_data(&mut self) {
+        unsafe {
+            av_packet_free_side_data(&mut self.0);
+        }
+    }
+
     #[inline]
     pub fn data(&self) -> Option<&[u8]> {
         unsafe {
@@ -316,3 +375,62 @@ impl<'a> Iterator for SideDataIter<'a> {
 }
 
 impl<'a> ExactSizeIterator for SideDataIter<'a> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use side_data::Type;
+
+    fn entries(packet: &Packet) -> Vec<(Type, Vec<u8>)> {
+        packet
+            .side_data()
+            .map(|side_data| (side_data.kind(), side_data.data().to_vec()))
+            .collect()
+    }
+
+    #[test]
+    fn side_data() {
+        let mut packet = Packet::empty();
+        packet.add_side_data(Type::NewExtraData, b"first").unwrap();
+        packet.add_side_data(Type::SkipSamples, b"middle").unwrap();
+        packet
+            .add_side_data(Type::StringsMetadata, b"second")
+            .unwrap();
+        packet.add_side_data(Type::Palette, b"palette").unwrap();
+        packet
+            .add_side_data(Type::WebVTTIdentifier, b"cue")
+            .unwrap();
+
+        // entries of a type are unique when added, duplicate one by hand
+        unsafe {
+            (*(*packet.as_mut_ptr()).side_data.add(2)).type_ = Type::NewExtraData.into();
+        }
+
+        packet.remove_side_data(Type::SkipSamples);
+        packet.remove_side_data(Type::NewExtraData);
+        packet.remove_side_data(Type::MPEGTSStreamID);
+        assert_eq!(
+            entries(&packet),
+            vec![
+                (Type::Palette, b"palette".to_vec()),
+                (Type::WebVTTIdentifier, b"cue".to_vec()),
+            ]
+        );
+
+        packet.side_data_mut(Type::Palette).unwrap()[0] = b'P';
+        assert!(packet.side_data_mut(Type::SkipSamples).is_none());
+        packet
+            .add_side_data(Type::WebVTTIdentifier, b"other")
+            .unwrap();
+        assert_eq!(
+            entries(&packet),
+            vec![
+                (Type::Palette, b"Palette".to_vec()),
+                (Type::WebVTTIdentifier, b"other".to_vec()),
+            ]
+        );
+
+        packet.clear_side_data();
+        assert_eq!(packet.side_data().count(), 0);
+    }
+}
@@ -3,6 +3,7 @@ use std::slice;
 
 use super::Packet;
 use crate::ffi::*;
+use crate::{utils, Error};
 #[cfg(feature = "serialize")]
 use serde::{Deserialize, Serialize};
 
@@ -256,12 +257,159 @@ impl<'a> SideData<'a> {
     pub fn data(&self) -> &[u8] {
         unsafe { slice::from_raw_parts((*self.as_ptr()).data, (*self.as_ptr()).size as usize) }
     }
+
+    /// Reads the payload as a `T` if this entry is of the given type.
+    pub(crate) unsafe fn payload<T: Copy>(&self, kind: Type) -> Option<T> {
+        if (*self.as_ptr()).type_ != AVPacketSideDataType::from(kind) {
+            return None;
+        }
+
+        utils::read_struct(self.data())
+    }
+}
+
+/// Stream parameters changed by a `ParamChange` entry.
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Default)]
+pub struct ParamChange {
+    pub sample_rate: Option<u32>,
+    /// Width and height.
+    pub dimensions: Option<(u32, u32)>,
+}
+
+// channel count and layout changes were removed in FFmpeg 7.0
+const PARAM_CHANGE_CHANNEL_COUNT: u32 = 0x0001;
+const PARAM_CHANGE_CHANNEL_LAYOUT: u32 = 0x0002;
+const PARAM_CHANGE_SAMPLE_RATE: u32 = AVSideDataParamChangeFlags::SAMPLE_RATE.0 as _;
+const PARAM_CHANGE_DIMENSIONS: u32 = AVSideDataParamChangeFlags::DIMENSIONS.0 as _;
+
+impl ParamChange {
+    fn from_bytes(data: &[u8]) -> Option<Self> {
+        let mut words = data
+            .chunks_exact(4)
+            .map(|word| u32::from_le_bytes(word.try_into().unwrap()));
+        let flags = words.next()?;
+        let mut value = ParamChange::default();
+
+        if flags & PARAM_CHANGE_CHANNEL_COUNT != 0 {
+            words.next()?;
+        }
+        if flags & PARAM_CHANGE_CHANNEL_LAYOUT != 0 {
+            words.next()?;
+            words.next()?;
+        }
+        if flags & PARAM_CHANGE_SAMPLE_RATE != 0 {
+            value.sample_rate = Some(words.next()?);
+        }
+        if flags & PARAM_CHANGE_DIMENSIONS != 0 {
+            value.dimensions = Some((words.next()?, words.next()?));
+        }
+
+        Some(value)
+    }
+
+    fn to_bytes(self) -> Vec<u8> {
+        let mut flags = 0;
+        let mut words = Vec::new();
+
+        if let Some(sample_rate) = self.sample_rate {
+            flags |= PARAM_CHANGE_SAMPLE_RATE;
+            words.push(sample_rate);
+        }
+        if let Some((width, height)) = self.dimensions {
+            flags |= PARAM_CHANGE_DIMENSIONS;
+            words.extend([width, height]);
+        }
+
+        std::iter::once(flags)
+            .chain(words)
+            .flat_map(u32::to_le_bytes)
+            .collect()
+    }
+}
+
+/// Samples to discard from a decoded audio packet, as set by a `SkipSamples`
+/// entry.
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Default)]
+pub struct SkipSamples {
+    /// Samples to skip from the start of the packet.
+    pub start: u32,
+    /// Samples to skip from the end of the packet.
+    pub end: u32,
+}
+
+impl SkipSamples {
+    fn from_bytes(data: &[u8]) -> Option<Self> {
+        Some(SkipSamples {
+            start: u32::from_le_bytes(data.get(0..4)?.try_into().ok()?),
+            end: u32::from_le_bytes(data.get(4..8)?.try_into().ok()?),
+        })
+    }
+
+    fn to_bytes(self) -> [u8; 10] {
+        let mut data = [0; 10];
+        data[0..4].copy_from_slice(&self.start.to_le_bytes());
+        data[4..8].copy_from_slice(&self.end.to_le_bytes());
+
+        data
+    }
+}
+
+impl<'a> SideData<'a> {
+    /// Returns the new codec extradata if this is a `NewExtraData` entry.
+    pub fn new_extradata(&self) -> Option<&[u8]> {
+        (self.kind() == Type::NewExtraData).then(|| self.data())
+    }
+
+    pub fn param_change(&self) -> Option<ParamChange> {
+        if self.kind() != Type::ParamChange {
+            return None;
+        }
+
+        ParamChange::from_bytes(self.data())
+    }
+
+    pub fn skip_samples(&self) -> Option<SkipSamples> {
+        if self.kind() != Type::SkipSamples {
+            return None;
+        }
+
+        SkipSamples::from_bytes(self.data())
+    }
+
+    pub fn mpegts_stream_id(&self) -> Option<u8> {
+        if self.kind() != Type::MPEGTSStreamID {
+            return None;
+        }
+
+        self.data().first().copied()
+    }
+}
+
+impl Packet {
+    /// Sets new codec extradata, e.g. for a resolution change mid-stream.
+    pub fn set_new_extradata(&mut self, value: &[u8]) -> Result<(), Error> {
+        self.add_side_data(Type::NewExtraData, value)
+    }
+
+    pub fn set_param_change(&mut self, value: ParamChange) -> Result<(), Error> {
+        self.add_side_data(Type::ParamChange, &value.to_bytes())
+    }
+
+    pub fn set_skip_samples(&mut self, value: SkipSamples) -> Result<(), Error> {
+        self.add_side_data(Type::SkipSamples, &value.to_bytes())
+    }
+
+    /// Sets the PES stream ID the MPEG-TS muxer writes for this packet.
+    pub fn set_mpegts_stream_id(&mut self, value: u8) -> Result<(), Error> {
+        self.add_side_data(Type::MPEGTSStreamID, &[value])
+    }
 }
 
-#[cfg(all(test, feature = "ffmpeg_9_0"))]
+#[cfg(test)]
 mod tests {
     use super::*;
 
+    #[cfg(feature = "ffmpeg_9_0")]
     #[test]
     fn ffmpeg_9_side_data_round_trips() {
         for kind in [Type::DYNAMIC_HDR_SMPTE_2094_APP5, Type::HEVC_CONF] {
@@ -269,4 +417,32 @@ mod tests {
             assert_eq!(Type::from(raw), kind);
         }
     }
+
+    #[test]
+    fn param_change() {
+        let value = ParamChange {
+            sample_rate: Some(48000),
+            dimensions: Some((1920, 1080)),
+        };
+        let data = value.to_bytes();
+
+        assert_eq!(data.len(), 16);
+        assert_eq!(ParamChange::from_bytes(&data), Some(value));
+        assert_eq!(ParamChange::from_bytes(&data[..12]), None);
+
+        // legacy channel count
+        let data = [1, 0, 0, 0, 2, 0, 0, 0];
+        assert_eq!(ParamChange::from_bytes(&data), Some(ParamChange::default()));
+    }
+
+    #[test]
+    fn skip_samples() {
+        let value = SkipSamples {
+            start: 1024,
+            end: 312,
+        };
+
+        assert_eq!(SkipSamples::from_bytes(&value.to_bytes()), Some(value));
+        assert_eq!(SkipSamples::from_bytes(&[0; 7]), None);
+    }
 }
@@ -7,6 +7,13 @@ use crate::color;
 use crate::media;
 use crate::{FieldOrder, Rational};
 
+#[cfg(feature = "ffmpeg_6_1")]
+use crate::codec::packet;
+#[cfg(feature = "ffmpeg_6_1")]
+use crate::ffi::*;
+#[cfg(feature = "ffmpeg_6_1")]
+use crate::Error;
+
 #[cfg(feature = "ffmpeg_8_1")]
 use crate::format::AlphaMode;
 
@@ -24,7 +31,6 @@ impl_for_many! {
 
         // TODO: codec_tag
         // TODO: extradata
-        // TODO: coded_side_data
         // TODO: format (needs From<c_int> for format::Pixel and format::Sample)
 
         pub fn bit_rate(&self) -> i64 {
@@ -142,5 +148,77 @@ impl_for_many! {
         pub fn alpha_mode(&self) -> AlphaMode {
             unsafe { (*self.as_ptr()).alpha_mode.into() }
         }
+
+        /// Returns the coded side data entry of the given type, if present.
+        #[cfg(feature = "ffmpeg_6_1")]
+        pub fn coded_side_data(&self, kind: packet::side_data::Type) -> Option<packet::SideData<'_>> {
+            unsafe {
+                let ptr = av_packet_side_data_get(
+                    (*self.as_ptr()).coded_side_data,
+                    (*self.as_ptr()).nb_coded_side_data,
+                    kind.into(),
+                );
+
+                if ptr.is_null() {
+                    None
+                } else {
+                    Some(packet::SideData::wrap(ptr as *mut _))
+                }
+            }
+        }
+    }
+}
+
+impl_for_many! {
+    impl for Parameters, ParametersMut<'p> {
+        /// Adds a coded side data entry, replacing any existing entry of the same type.
+        #[cfg(feature = "ffmpeg_6_1")]
+        pub fn add_coded_side_data(&mut self, kind: packet::side_data::Type, data: &[u8]) -> Result<(), Error> {
+            unsafe {
+                let ptr = self.new_coded_side_data(kind, data.len())?;
+                std::ptr::copy_nonoverlapping(data.as_ptr(), ptr, data.len());
+            }
+
+            Ok(())
+        }
+
+        /// Removes all coded side data entries of the given type.
+        #[cfg(feature = "ffmpeg_6_1")]
+        pub fn remove_coded_side_data(&mut self, kind: packet::side_data::Type) {
+            unsafe {
+                let ptr = self.as_mut_ptr();
+                av_packet_side_data_remove(
+                    (*ptr).coded_side_data,
+                    &mut (*ptr).nb_coded_side_data,
+                    kind.into(),
+                );
+            }
+        }
+
+        /// Allocates a coded side data entry of `size` bytes, replacing any existing entry
+        /// of the same type, and returns a pointer to its payload.
+        #[cfg(feature = "ffmpeg_6_1")]
+        pub(crate) unsafe fn new_coded_side_data(
+            &mut self,
+            kind: packet::side_data::Type,
+            size: usize,
+        ) -> Result<*mut u8, Error> {
+            let ptr = self.as_mut_ptr();
+            let side_data = av_packet_side_data_new(
+                &mut (*ptr).coded_side_data,
+                &mut (*ptr).nb_coded_side_data,
+                kind.into(),
+                size,
+                0,
+            );
+
+            if side_data.is_null() {
+                Err(Error::Other {
+                    errno: libc::ENOMEM,
+                })
+            } else {
+                Ok((*side_data).data)
+            }
+        }
     }
 }
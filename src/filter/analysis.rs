@@ -0,0 +1,612 @@
+//! Typed results of the analysis filters, which report through frame metadata
+//! keys such as `lavfi.black_start`.
+//!
+//! An [`Analyzer`] runs one analysis filter over a stream of frames and turns
+//! the metadata of its output frames into [`Event`]s.
+
+use std::ffi::CString;
+use std::str::FromStr;
+
+use super::Graph;
+use crate::ffi::*;
+use crate::{format, media, ChannelLayout, DictionaryRef, Error, Frame, Rational};
+
+/// Analysis filter run by an [`Analyzer`].
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum Kind {
+    /// `blackdetect`, reporting [`Event::Black`].
+    Black,
+    /// `silencedetect`, reporting [`Event::Silence`].
+    Silence,
+    /// `cropdetect`, reporting [`Event::Crop`].
+    Crop,
+    /// `scdet`, reporting [`Event::Scene`].
+    Scene,
+    /// `ebur128`, reporting [`Event::Loudness`].
+    Loudness,
+    /// `signalstats`, reporting [`Event::SignalStats`].
+    SignalStats,
+    /// `idet`, reporting [`Event::Interlace`].
+    Interlace,
+}
+
+impl Kind {
+    pub fn filter_name(self) -> &'static str {
+        match self {
+            Kind::Black => "blackdetect",
+            Kind::Silence => "silencedetect",
+            Kind::Crop => "cropdetect",
+            Kind::Scene => "scdet",
+            Kind::Loudness => "ebur128",
+            Kind::SignalStats => "signalstats",
+            Kind::Interlace => "idet",
+        }
+    }
+
+    pub fn medium(self) -> media::Type {
+        match self {
+            Kind::Silence | Kind::Loudness => media::Type::Audio,
+            _ => media::Type::Video,
+        }
+    }
+
+    /// Options the filter needs to export its results as metadata.
+    fn required_args(self) -> &'static str {
+        match self {
+            Kind::Loudness => "metadata=1:peak=true",
+            _ => "",
+        }
+    }
+}
+
+/// A time interval, in seconds.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct Segment {
+    pub start: f64,
+    pub end: f64,
+}
+
+impl Segment {
+    pub fn duration(&self) -> f64 {
+        self.end - self.start
+    }
+}
+
+/// Non-black area of a frame, as found by `cropdetect`.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct Crop {
+    pub time: Option<f64>,
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Scene change score of a frame, as computed by `scdet`.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct Scene {
+    pub time: Option<f64>,
+    /// Score in `0..=100`.
+    pub score: f64,
+    /// Mean absolute frame difference with the previous frame.
+    pub mafd: f64,
+    /// Whether the score is above the detection threshold.
+    pub change: bool,
+}
+
+/// EBU R128 loudness measured by `ebur128`, up to the current frame.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct Loudness {
+    pub time: Option<f64>,
+    /// Momentary loudness, in LUFS.
+    pub momentary: f64,
+    /// Short-term loudness, in LUFS.
+    pub short_term: f64,
+    /// Integrated loudness, in LUFS.
+    pub integrated: f64,
+    /// Loudness range, in LU.
+    pub range: f64,
+    /// Highest true peak so far over all channels, in dBTP.
+    pub true_peak: Option<f64>,
+}
+
+/// Distribution of a value over the pixels of a frame.
+#[derive(PartialEq, Clone, Copy, Debug, Default)]
+pub struct Statistics {
+    pub min: f64,
+    /// 10th percentile.
+    pub low: f64,
+    pub average: f64,
+    /// 90th percentile.
+    pub high: f64,
+    pub max: f64,
+}
+
+/// Per-frame statistics computed by `signalstats`.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct SignalStats {
+    pub time: Option<f64>,
+    pub y: Statistics,
+    pub u: Statistics,
+    pub v: Statistics,
+    pub saturation: Statistics,
+    pub hue_median: f64,
+    pub hue_average: f64,
+    /// Mean absolute luma difference with the previous frame.
+    pub y_difference: f64,
+}
+
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum FieldOrder {
+    TopFirst,
+    BottomFirst,
+    Progressive,
+    Undetermined,
+}
+
+impl FieldOrder {
+    fn from_metadata(value: &str) -> Option<Self> {
+        match value {
+            "tff" => Some(FieldOrder::TopFirst),
+            "bff" => Some(FieldOrder::BottomFirst),
+            "progressive" => Some(FieldOrder::Progressive),
+            "undetermined" => Some(FieldOrder::Undetermined),
+            _ => None,
+        }
+    }
+}
+
+/// Number of frames detected with each field order so far.
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Default)]
+pub struct FieldCounts {
+    pub top_first: u64,
+    pub bottom_first: u64,
+    pub progressive: u64,
+    pub undetermined: u64,
+}
+
+/// Interlacing detected by `idet`.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct Interlace {
+    pub time: Option<f64>,
+    /// Field order of the current frame, looking at it alone.
+    pub single: FieldOrder,
+    /// Field order of the current frame, taking the previous ones into
+    /// account.
+    pub multiple: FieldOrder,
+    pub single_counts: FieldCounts,
+    pub multiple_counts: FieldCounts,
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Event {
+    Black(Segment),
+    Silence(Segment),
+    Crop(Crop),
+    Scene(Scene),
+    Loudness(Loudness),
+    SignalStats(SignalStats),
+    Interlace(Interlace),
+}
+
+/// Runs an analysis filter over frames.
+///
+/// Frames are passed to [`send`](Analyzer::send) in presentation order, and
+/// [`flush`](Analyzer::flush) returns the events of the frames still buffered
+/// by the filter, closing any open black or silence segment. Black and
+/// silence segments shorter than the minimum duration of the filter, `d`, are
+/// dropped.
+pub struct Analyzer {
+    graph: Graph,
+    kind: Kind,
+    time_base: Rational,
+    min_duration: f64,
+
+    frame: Frame,
+    open: Option<f64>,
+    last: Option<f64>,
+    peak: Option<f64>,
+}
+
+impl Analyzer {
+    /// Analyzes video frames of the given format and size.
+    ///
+    /// `options` are appended to the options of the filter, e.g. `pix_th=0.05`
+    /// for the luma threshold of black pixels.
+    pub fn video(
+        kind: Kind,
+        options: &str,
+        format: format::Pixel,
+        width: u32,
+        height: u32,
+        time_base: Rational,
+    ) -> Result<Self, Error> {
+        let args = format!(
+            "video_size={width}x{height}:pix_fmt={}:time_base={time_base}",
+            AVPixelFormat::from(format).0,
+        );
+
+        Analyzer::new(kind, options, media::Type::Video, &args, time_base)
+    }
+
+    /// Analyzes audio frames of the given format, rate and layout.
+    pub fn audio(
+        kind: Kind,
+        options: &str,
+        format: format::Sample,
+        sample_rate: u32,
+        ch_layout: &ChannelLayout,
+        time_base: Rational,
+    ) -> Result<Self, Error> {
+        let args = format!(
+            "time_base={time_base}:sample_rate={sample_rate}:sample_fmt={}:channel_layout={}",
+            format.name(),
+            ch_layout.description(),
+        );
+
+        Analyzer::new(kind, options, media::Type::Audio, &args, time_base)
+    }
+
+    fn new(
+        kind: Kind,
+        options: &str,
+        medium: media::Type,
+        source_args: &str,
+        time_base: Rational,
+    ) -> Result<Self, Error> {
+        if kind.medium() != medium {
+            return Err(Error::Other {
+                errno: libc::EINVAL,
+            });
+        }
+
+        let (source, sink) = match medium {
+            media::Type::Video => ("buffer", "buffersink"),
+            _ => ("abuffer", "abuffersink"),
+        };
+        let args = match (kind.required_args(), options) {
+            (required, "") => required.to_owned(),
+            ("", options) => options.to_owned(),
+            (required, options) => format!("{required}:{options}"),
+        };
+
+        let mut graph = Graph::new();
+        graph.add(
+            &super::find(source).ok_or(Error::FilterNotFound)?,
+            "in",
+            source_args,
+        )?;
+        graph.add(
+            &super::find(kind.filter_name()).ok_or(Error::FilterNotFound)?,
+            "analysis",
+            &args,
+        )?;
+        graph.add(&super::find(sink).ok_or(Error::FilterNotFound)?, "out", "")?;
+        graph.link("in", 0, "analysis", 0)?;
+        graph.link("analysis", 0, "out", 0)?;
+        graph.validate()?;
+
+        let min_duration = unsafe {
+            let mut analysis = graph.get("analysis").unwrap();
+            min_duration(kind, analysis.as_mut_ptr())?
+        };
+
+        Ok(Analyzer {
+            graph,
+            kind,
+            time_base,
+            min_duration,
+            frame: unsafe { Frame::empty() },
+            open: None,
+            last: None,
+            peak: None,
+        })
+    }
+
+    pub fn kind(&self) -> Kind {
+        self.kind
+    }
+
+    /// Analyzes `frame`, returning the events of the frames the filter
+    /// released.
+    pub fn send(&mut self, frame: &Frame) -> Result<Vec<Event>, Error> {
+        self.graph.buffer_source("in")?.add_ref(frame, None)?;
+        self.drain()
+    }
+
+    /// Signals the end of the stream, returning the remaining events.
+    pub fn flush(&mut self) -> Result<Vec<Event>, Error> {
+        self.graph.buffer_source("in")?.flush()?;
+        let mut events = self.drain()?;
+
+        if let (Some(start), Some(end)) = (self.open.take(), self.last) {
+            events.extend(segment(
+                self.kind,
+                Segment { start, end },
+                self.min_duration,
+            ));
+        }
+
+        Ok(events)
+    }
+
+    fn drain(&mut self) -> Result<Vec<Event>, Error> {
+        let mut events = Vec::new();
+        let mut sink = self.graph.buffer_sink("out")?;
+
+        loop {
+            match sink.frame(&mut self.frame) {
+                Ok(()) => (),
+                Err(Error::Eof) => break,
+                Err(Error::Other { errno }) if errno == libc::EAGAIN => break,
+                Err(e) => return Err(e),
+            }
+
+            let time = self
+                .frame
+                .pts()
+                .map(|pts| pts as f64 * f64::from(self.time_base));
+            self.last = time.or(self.last);
+
+            let metadata = self.frame.metadata();
+
+            match self.kind {
+                Kind::Black | Kind::Silence => {
+                    let prefix = match self.kind {
+                        Kind::Black => "lavfi.black",
+                        _ => "lavfi.silence",
+                    };
+
+                    if let Some(start) = parse(&metadata, &format!("{prefix}_start")) {
+                        self.open = Some(start);
+                    }
+
+                    if let Some(end) = parse(&metadata, &format!("{prefix}_end")) {
+                        if let Some(start) = self.open.take() {
+                            events.extend(segment(
+                                self.kind,
+                                Segment { start, end },
+                                self.min_duration,
+                            ));
+                        }
+                    }
+                }
+                Kind::Crop => events.extend(crop(&metadata, time).map(Event::Crop)),
+                Kind::Scene => events.extend(scene(&metadata, time).map(Event::Scene)),
+                Kind::Loudness => {
+                    if let Some(mut loudness) = loudness(&metadata, time) {
+                        // the filter exports the peak of the current frame only
+                        loudness.true_peak = match (self.peak, loudness.true_peak) {
+                            (Some(peak), Some(current)) => Some(peak.max(current)),
+                            (peak, current) => peak.or(current),
+                        };
+                        self.peak = loudness.true_peak;
+
+                        events.push(Event::Loudness(loudness));
+                    }
+                }
+                Kind::SignalStats => {
+                    events.extend(signal_stats(&metadata, time).map(Event::SignalStats))
+                }
+                Kind::Interlace => events.extend(interlace(&metadata, time).map(Event::Interlace)),
+            }
+
+            unsafe {
+                av_frame_unref(self.frame.as_mut_ptr());
+            }
+        }
+
+        Ok(events)
+    }
+}
+
+/// Reads the minimum duration of black or silence segments, in seconds, from
+/// the options of the `context` running `kind`.
+unsafe fn min_duration(kind: Kind, context: *mut AVFilterContext) -> Result<f64, Error> {
+    match kind {
+        Kind::Black => {
+            let name = CString::new("black_min_duration").unwrap();
+            let mut value = 0.0;
+
+            match av_opt_get_double(
+                context as *mut _,
+                name.as_ptr(),
+                AV_OPT_SEARCH_CHILDREN,
+                &mut value,
+            ) {
+                0 => Ok(value),
+                e => Err(Error::from(e)),
+            }
+        }
+        Kind::Silence => {
+            let name = CString::new("duration").unwrap();
+            let mut value = 0;
+
+            // a duration option, in microseconds
+            match av_opt_get_int(
+                context as *mut _,
+                name.as_ptr(),
+                AV_OPT_SEARCH_CHILDREN,
+                &mut value,
+            ) {
+                0 => Ok(value as f64 / 1_000_000.0),
+                e => Err(Error::from(e)),
+            }
+        }
+        _ => Ok(0.0),
+    }
+}
+
+/// Returns the event of a black or silence segment, or `None` if it is
+/// shorter than `min_duration`.
+fn segment(kind: Kind, segment: Segment, min_duration: f64) -> Option<Event> {
+    if segment.duration() < min_duration {
+        return None;
+    }
+
+    match kind {
+        Kind::Black => Some(Event::Black(segment)),
+        Kind::Silence => Some(Event::Silence(segment)),
+        _ => None,
+    }
+}
+
+fn parse<T: FromStr>(metadata: &DictionaryRef, key: &str) -> Option<T> {
+    metadata.get(key)?.trim().parse().ok()
+}
+
+fn crop(metadata: &DictionaryRef, time: Option<f64>) -> Option<Crop> {
+    Some(Crop {
+        time,
+        x: parse(metadata, "lavfi.cropdetect.x")?,
+        y: parse(metadata, "lavfi.cropdetect.y")?,
+        width: parse(metadata, "lavfi.cropdetect.w")?,
+        height: parse(metadata, "lavfi.cropdetect.h")?,
+    })
+}
+
+fn scene(metadata: &DictionaryRef, time: Option<f64>) -> Option<Scene> {
+    Some(Scene {
+        time,
+        score: parse(metadata, "lavfi.scd.score")?,
+        mafd: parse(metadata, "lavfi.scd.mafd")?,
+        change: metadata.get("lavfi.scd.time").is_some(),
+    })
+}
+
+fn loudness(metadata: &DictionaryRef, time: Option<f64>) -> Option<Loudness> {
+    Some(Loudness {
+        time,
+        momentary: parse(metadata, "lavfi.r128.M")?,
+        short_term: parse(metadata, "lavfi.r128.S")?,
+        integrated: parse(metadata, "lavfi.r128.I")?,
+        range: parse(metadata, "lavfi.r128.LRA")?,
+        // exported as a linear amplitude
+        true_peak: parse::<f64>(metadata, "lavfi.r128.true_peak").map(|peak| 20.0 * peak.log10()),
+    })
+}
+
+fn signal_stats(metadata: &DictionaryRef, time: Option<f64>) -> Option<SignalStats> {
+    let statistics = |name: &str| {
+        let key = |suffix: &str| format!("lavfi.signalstats.{name}{suffix}");
+
+        Some(Statistics {
+            min: parse(metadata, &key("MIN"))?,
+            low: parse(metadata, &key("LOW"))?,
+            average: parse(metadata, &key("AVG"))?,
+            high: parse(metadata, &key("HIGH"))?,
+            max: parse(metadata, &key("MAX"))?,
+        })
+    };
+
+    Some(SignalStats {
+        time,
+        y: statistics("Y")?,
+        u: statistics("U")?,
+        v: statistics("V")?,
+        saturation: statistics("SAT")?,
+        hue_median: parse(metadata, "lavfi.signalstats.HUEMED")?,
+        hue_average: parse(metadata, "lavfi.signalstats.HUEAVG")?,
+        y_difference: parse(metadata, "lavfi.signalstats.YDIF")?,
+    })
+}
+
+fn interlace(metadata: &DictionaryRef, time: Option<f64>) -> Option<Interlace> {
+    let counts = |kind: &str| {
+        let key = |order: &str| format!("lavfi.idet.{kind}.{order}");
+
+        Some(FieldCounts {
+            top_first: parse(metadata, &key("tff"))?,
+            bottom_first: parse(metadata, &key("bff"))?,
+            progressive: parse(metadata, &key("progressive"))?,
+            undetermined: parse(metadata, &key("undetermined"))?,
+        })
+    };
+
+    Some(Interlace {
+        time,
+        single: FieldOrder::from_metadata(metadata.get("lavfi.idet.single.current_frame")?)?,
+        multiple: FieldOrder::from_metadata(metadata.get("lavfi.idet.multiple.current_frame")?)?,
+        single_counts: counts("single")?,
+        multiple_counts: counts("multiple")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Dictionary;
+
+    #[test]
+    fn test_metadata() {
+        let mut metadata = Dictionary::new();
+        metadata.set("lavfi.cropdetect.x", "8");
+        metadata.set("lavfi.cropdetect.y", "4");
+        metadata.set("lavfi.cropdetect.w", "1904");
+        metadata.set("lavfi.cropdetect.h", "1072");
+        metadata.set("lavfi.r128.M", "-23.100");
+        metadata.set("lavfi.r128.S", "-22.900");
+        metadata.set("lavfi.r128.I", "-inf");
+        metadata.set("lavfi.r128.LRA", "0.000");
+        metadata.set("lavfi.r128.true_peak", "1.000");
+
+        let metadata = metadata.as_ref();
+        assert_eq!(
+            crop(&metadata, Some(1.0)),
+            Some(Crop {
+                time: Some(1.0),
+                x: 8,
+                y: 4,
+                width: 1904,
+                height: 1072,
+            })
+        );
+
+        let loudness = loudness(&metadata, None).unwrap();
+        assert_eq!(loudness.momentary, -23.1);
+        assert_eq!(loudness.integrated, f64::NEG_INFINITY);
+        assert_eq!(loudness.true_peak, Some(0.0));
+
+        assert_eq!(scene(&metadata, None), None);
+    }
+
+    #[test]
+    fn test_black() {
+        let time_base = Rational(1, 25);
+        let run = |options: &str| {
+            let mut analyzer = Analyzer::video(
+                Kind::Black,
+                options,
+                format::Pixel::YUV420P,
+                16,
+                16,
+                time_base,
+            )
+            .unwrap();
+            let mut events = Vec::new();
+
+            for pts in 0..10 {
+                let mut frame = crate::frame::Video::new(format::Pixel::YUV420P, 16, 16);
+                for plane in 0..frame.planes() {
+                    frame.data_mut(plane).fill(0);
+                }
+                frame.set_pts(Some(pts));
+
+                events.extend(analyzer.send(&frame).unwrap());
+            }
+            events.extend(analyzer.flush().unwrap());
+
+            events
+        };
+
+        // shorter than the default minimum duration of 2 seconds
+        assert_eq!(run(""), vec![]);
+        assert_eq!(
+            run("d=0.2"),
+            vec![Event::Black(Segment {
+                start: 0.0,
+                end: 9.0 * f64::from(time_base),
+            })]
+        );
+    }
+}
@@ -0,0 +1,42 @@
+//! Commands changing filter parameters in a running graph, see
+//! [`Graph::send_command`](super::Graph::send_command).
+
+use crate::ffi::*;
+use libc::c_int;
+
+bitflags::bitflags! {
+    pub struct Flags: c_int {
+        /// Stop once a filter accepted the command.
+        const ONE  = AVFILTER_CMD_FLAG_ONE;
+        /// Only run the command if it is fast, e.g. no reinitialization.
+        const FAST = AVFILTER_CMD_FLAG_FAST;
+    }
+}
+
+/// Target matching every filter in the graph.
+pub const ALL: &str = "all";
+
+/// Capacity of the buffer receiving command responses.
+pub(crate) const RESPONSE_SIZE: usize = 4096;
+
+/// Converts a response buffer filled by a filter into a string.
+pub(crate) fn response(buffer: &[u8]) -> String {
+    let length = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+
+    String::from_utf8_lossy(&buffer[..length]).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_response() {
+        let mut buffer = [0u8; 16];
+        buffer[..5].copy_from_slice(b"1.000");
+
+        assert_eq!(response(&buffer), "1.000");
+        assert_eq!(response(&[0; 4]), "");
+        assert_eq!(response(b"full"), "full");
+    }
+}
@@ -1,8 +1,12 @@
 use std::marker::PhantomData;
 
+use std::ffi::CString;
+
 use super::{Sink, Source};
 use crate::ffi::*;
-use crate::{format, option, AsMutPtr, AsPtr};
+use crate::filter::command;
+use crate::{format, option, AsMutPtr, AsPtr, Error};
+use libc::c_int;
 
 use crate::ChannelLayout;
 
@@ -33,10 +37,16 @@ impl<'a> Context<'a> {
 }
 
 impl<'a> Context<'a> {
+    /// Borrows the context for as long as the graph, so that only one handle
+    /// can exist at a time.
+    #[deprecated(note = "use `Graph::buffer_source` or `Graph::buffers`")]
     pub fn source(&'a mut self) -> Source<'a> {
         unsafe { Source::wrap(self) }
     }
 
+    /// Borrows the context for as long as the graph, so that only one handle
+    /// can exist at a time.
+    #[deprecated(note = "use `Graph::buffer_sink` or `Graph::buffers`")]
     pub fn sink(&'a mut self) -> Sink<'a> {
         unsafe { Sink::wrap(self) }
     }
@@ -61,6 +71,32 @@ impl<'a> Context<'a> {
     pub fn set_ch_layout(&mut self, value: ChannelLayout) {
         let _ = option::Settable::set_str(self, "channel_layouts", &value.description());
     }
+
+    /// Sends a command to this filter only and returns its response.
+    pub fn send_command(
+        &mut self,
+        cmd: &str,
+        arg: &str,
+        flags: command::Flags,
+    ) -> Result<String, Error> {
+        unsafe {
+            let cmd = CString::new(cmd).unwrap();
+            let arg = CString::new(arg).unwrap();
+            let mut response = [0u8; command::RESPONSE_SIZE];
+
+            match avfilter_process_command(
+                self.as_mut_ptr(),
+                cmd.as_ptr(),
+                arg.as_ptr(),
+                response.as_mut_ptr() as *mut _,
+                response.len() as c_int,
+                flags.bits(),
+            ) {
+                n if n >= 0 => Ok(command::response(&response)),
+                e => Err(Error::from(e)),
+            }
+        }
+    }
 }
 
 impl<'a> AsPtr<AVFilterContext> for Context<'a> {
@@ -0,0 +1,23 @@
+use crate::ffi::*;
+use libc::c_int;
+
+bitflags::bitflags! {
+    pub struct SourceFlags: c_int {
+        /// Skip the check for format changes against the configured parameters.
+        const NO_CHECK_FORMAT = AV_BUFFERSRC_FLAG_NO_CHECK_FORMAT.0 as _;
+        /// Immediately push the frame through the graph to the sinks.
+        const PUSH            = AV_BUFFERSRC_FLAG_PUSH.0 as _;
+        /// Add a new reference to the frame instead of taking over its references.
+        const KEEP_REF        = AV_BUFFERSRC_FLAG_KEEP_REF.0 as _;
+    }
+}
+
+bitflags::bitflags! {
+    pub struct SinkFlags: c_int {
+        /// Return a reference to the next frame without removing it from the sink.
+        const PEEK       = AV_BUFFERSINK_FLAG_PEEK;
+        /// Only return a frame that is already available, never request one
+        /// from the filters upstream.
+        const NO_REQUEST = AV_BUFFERSINK_FLAG_NO_REQUEST;
+    }
+}
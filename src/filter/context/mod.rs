@@ -1,8 +1,14 @@
 mod context;
 pub use self::context::Context;
 
+pub mod flag;
+pub use self::flag::{SinkFlags, SourceFlags};
+
+mod parameters;
+pub use self::parameters::{Format, Parameters};
+
 mod source;
-pub use self::source::Source;
+pub use self::source::{BufferSource, Source};
 
 mod sink;
-pub use self::sink::Sink;
+pub use self::sink::{BufferSink, Sink};
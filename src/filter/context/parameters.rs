@@ -0,0 +1,147 @@
+#[cfg(feature = "ffmpeg_7_1")]
+use crate::color;
+use crate::ffi::*;
+use crate::{format, ChannelLayout, Error, Rational};
+
+/// Frame format accepted by a buffer source.
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum Format {
+    Pixel(format::Pixel),
+    Sample(format::Sample),
+}
+
+/// Properties of the frames a `buffer` or `abuffer` source will receive, see
+/// [`Source::set_parameters`](super::Source::set_parameters).
+///
+/// Unset (`None` or zero) fields keep the value the source already has.
+#[derive(Clone, Debug)]
+pub struct Parameters {
+    pub format: Option<Format>,
+    pub time_base: Option<Rational>,
+
+    pub width: u32,
+    pub height: u32,
+    pub aspect_ratio: Option<Rational>,
+    pub frame_rate: Option<Rational>,
+    hw_frames_ctx: *mut AVBufferRef,
+    #[cfg(feature = "ffmpeg_7_1")]
+    pub color_space: Option<color::Space>,
+    #[cfg(feature = "ffmpeg_7_1")]
+    pub color_range: Option<color::Range>,
+
+    pub sample_rate: u32,
+    pub ch_layout: Option<ChannelLayout<'static>>,
+}
+
+impl Parameters {
+    /// Parameters for video frames of the given format and size.
+    pub fn video(format: format::Pixel, width: u32, height: u32, time_base: Rational) -> Self {
+        Parameters {
+            format: Some(Format::Pixel(format)),
+            time_base: Some(time_base),
+            width,
+            height,
+            ..Default::default()
+        }
+    }
+
+    /// Parameters for audio frames of the given format, rate and layout.
+    pub fn audio(
+        format: format::Sample,
+        sample_rate: u32,
+        ch_layout: ChannelLayout<'static>,
+        time_base: Rational,
+    ) -> Self {
+        Parameters {
+            format: Some(Format::Sample(format)),
+            time_base: Some(time_base),
+            sample_rate,
+            ch_layout: Some(ch_layout),
+            ..Default::default()
+        }
+    }
+
+    pub fn hw_frames_ctx(&self) -> *mut AVBufferRef {
+        self.hw_frames_ctx
+    }
+
+    /// Sets the hardware frames context of the input frames, which the source
+    /// takes a new reference to.
+    ///
+    /// # Safety
+    ///
+    /// `value` must be null or a valid `AVHWFramesContext` reference that
+    /// outlives every [`Source::set_parameters`](super::Source::set_parameters)
+    /// call made with these parameters.
+    pub unsafe fn set_hw_frames_ctx(&mut self, value: *mut AVBufferRef) {
+        self.hw_frames_ctx = value;
+    }
+
+    /// Fills an `AVBufferSrcParameters` from `av_buffersrc_parameters_alloc`.
+    pub(crate) unsafe fn fill(&self, raw: *mut AVBufferSrcParameters) -> Result<(), Error> {
+        match self.format {
+            Some(Format::Pixel(format)) => (*raw).format = AVPixelFormat::from(format).0 as _,
+            Some(Format::Sample(format)) => (*raw).format = AVSampleFormat::from(format).0 as _,
+            None => (),
+        }
+
+        if let Some(time_base) = self.time_base {
+            (*raw).time_base = time_base.into();
+        }
+
+        (*raw).width = self.width as _;
+        (*raw).height = self.height as _;
+
+        if let Some(aspect_ratio) = self.aspect_ratio {
+            (*raw).sample_aspect_ratio = aspect_ratio.into();
+        }
+
+        if let Some(frame_rate) = self.frame_rate {
+            (*raw).frame_rate = frame_rate.into();
+        }
+
+        (*raw).hw_frames_ctx = self.hw_frames_ctx;
+
+        #[cfg(feature = "ffmpeg_7_1")]
+        {
+            if let Some(color_space) = self.color_space {
+                (*raw).color_space = color_space.into();
+            }
+
+            if let Some(color_range) = self.color_range {
+                (*raw).color_range = color_range.into();
+            }
+        }
+
+        (*raw).sample_rate = self.sample_rate as _;
+
+        if let Some(ch_layout) = &self.ch_layout {
+            match av_channel_layout_copy(&mut (*raw).ch_layout, ch_layout.as_ptr()) {
+                0 => (),
+                e => return Err(Error::from(e)),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for Parameters {
+    fn default() -> Self {
+        Parameters {
+            format: None,
+            time_base: None,
+            width: 0,
+            height: 0,
+            aspect_ratio: None,
+            frame_rate: None,
+            hw_frames_ctx: std::ptr::null_mut(),
+            #[cfg(feature = "ffmpeg_7_1")]
+            color_space: None,
+            #[cfg(feature = "ffmpeg_7_1")]
+            color_range: None,
+            sample_rate: 0,
+            ch_layout: None,
+        }
+    }
+}
@@ -1,22 +1,67 @@
-use super::Context;
+use std::mem;
+
+use std::marker::PhantomData;
+
+use super::{Context, SinkFlags};
+#[cfg(feature = "ffmpeg_7_1")]
+use crate::color;
 use crate::ffi::*;
-use crate::{Error, Frame};
+use crate::{format, media, ChannelLayout, Error, Frame, Rational};
 use libc::c_int;
 
-pub struct Sink<'a> {
-    ctx: &'a mut Context<'a>,
+/// Handle to a buffer sink filter context, borrowing the graph that owns it.
+///
+/// Handles are obtained with [`Graph::buffer_sink`](crate::filter::Graph::buffer_sink),
+/// or together with [`Graph::buffers`](crate::filter::Graph::buffers).
+pub struct BufferSink<'a> {
+    ptr: *mut AVFilterContext,
+
+    _marker: PhantomData<&'a mut ()>,
 }
 
-impl<'a> Sink<'a> {
-    pub unsafe fn wrap<'b>(ctx: &'b mut Context<'b>) -> Sink<'b> {
-        Sink { ctx }
+/// Former name of [`BufferSink`].
+pub type Sink<'a> = BufferSink<'a>;
+
+impl<'a> BufferSink<'a> {
+    pub unsafe fn wrap<'b>(ctx: &'b mut Context<'b>) -> BufferSink<'b> {
+        BufferSink::from_raw(ctx.as_mut_ptr())
+    }
+
+    /// # Safety
+    ///
+    /// `ptr` must be a valid buffer sink context that outlives `'a`.
+    pub unsafe fn from_raw(ptr: *mut AVFilterContext) -> Self {
+        BufferSink {
+            ptr,
+            _marker: PhantomData,
+        }
+    }
+
+    pub unsafe fn as_ptr(&self) -> *const AVFilterContext {
+        self.ptr as *const _
+    }
+
+    pub unsafe fn as_mut_ptr(&mut self) -> *mut AVFilterContext {
+        self.ptr
     }
 }
 
-impl<'a> Sink<'a> {
+impl<'a> BufferSink<'a> {
     pub fn frame(&mut self, frame: &mut Frame) -> Result<(), Error> {
         unsafe {
-            match av_buffersink_get_frame(self.ctx.as_mut_ptr(), frame.as_mut_ptr()) {
+            match av_buffersink_get_frame(self.as_mut_ptr(), frame.as_mut_ptr()) {
+                n if n >= 0 => Ok(()),
+                e => Err(Error::from(e)),
+            }
+        }
+    }
+
+    /// Gets a frame with the given flags, failing with `Error::Other { errno:
+    /// EAGAIN }` if none is available.
+    pub fn get_frame_flags(&mut self, frame: &mut Frame, flags: SinkFlags) -> Result<(), Error> {
+        unsafe {
+            match av_buffersink_get_frame_flags(self.as_mut_ptr(), frame.as_mut_ptr(), flags.bits())
+            {
                 n if n >= 0 => Ok(()),
                 e => Err(Error::from(e)),
             }
@@ -25,11 +70,8 @@ impl<'a> Sink<'a> {
 
     pub fn samples(&mut self, frame: &mut Frame, samples: usize) -> Result<(), Error> {
         unsafe {
-            match av_buffersink_get_samples(
-                self.ctx.as_mut_ptr(),
-                frame.as_mut_ptr(),
-                samples as c_int,
-            ) {
+            match av_buffersink_get_samples(self.as_mut_ptr(), frame.as_mut_ptr(), samples as c_int)
+            {
                 n if n >= 0 => Ok(()),
                 e => Err(Error::from(e)),
             }
@@ -38,7 +80,80 @@ impl<'a> Sink<'a> {
 
     pub fn set_frame_size(&mut self, value: u32) {
         unsafe {
-            av_buffersink_set_frame_size(self.ctx.as_mut_ptr(), value);
+            av_buffersink_set_frame_size(self.as_mut_ptr(), value);
+        }
+    }
+
+    pub fn medium(&self) -> media::Type {
+        unsafe { media::Type::from(av_buffersink_get_type(self.as_ptr())) }
+    }
+
+    pub fn time_base(&self) -> Rational {
+        unsafe { Rational::from(av_buffersink_get_time_base(self.as_ptr())) }
+    }
+
+    pub fn pixel_format(&self) -> format::Pixel {
+        unsafe {
+            match av_buffersink_get_format(self.as_ptr()) {
+                -1 => format::Pixel::None,
+                n => format::Pixel::from(mem::transmute::<c_int, AVPixelFormat>(n)),
+            }
         }
     }
+
+    pub fn sample_format(&self) -> format::Sample {
+        unsafe {
+            match av_buffersink_get_format(self.as_ptr()) {
+                -1 => format::Sample::None,
+                n => format::Sample::from(mem::transmute::<c_int, AVSampleFormat>(n)),
+            }
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        unsafe { av_buffersink_get_w(self.as_ptr()) as u32 }
+    }
+
+    pub fn height(&self) -> u32 {
+        unsafe { av_buffersink_get_h(self.as_ptr()) as u32 }
+    }
+
+    pub fn aspect_ratio(&self) -> Rational {
+        unsafe { Rational::from(av_buffersink_get_sample_aspect_ratio(self.as_ptr())) }
+    }
+
+    pub fn frame_rate(&self) -> Rational {
+        unsafe { Rational::from(av_buffersink_get_frame_rate(self.as_ptr())) }
+    }
+
+    #[cfg(feature = "ffmpeg_7_1")]
+    pub fn color_space(&self) -> color::Space {
+        unsafe { color::Space::from(av_buffersink_get_colorspace(self.as_ptr())) }
+    }
+
+    #[cfg(feature = "ffmpeg_7_1")]
+    pub fn color_range(&self) -> color::Range {
+        unsafe { color::Range::from(av_buffersink_get_color_range(self.as_ptr())) }
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        unsafe { av_buffersink_get_sample_rate(self.as_ptr()) as u32 }
+    }
+
+    pub fn ch_layout(&self) -> Result<ChannelLayout<'static>, Error> {
+        unsafe {
+            let mut layout = AVChannelLayout::empty();
+
+            match av_buffersink_get_ch_layout(self.as_ptr(), &mut layout) {
+                0 => Ok(ChannelLayout::from(layout)),
+                e => Err(Error::from(e)),
+            }
+        }
+    }
+
+    /// Returns the hardware frames context of the output, or null for
+    /// software frames.
+    pub unsafe fn hw_frames_ctx(&self) -> *mut AVBufferRef {
+        av_buffersink_get_hw_frames_ctx(self.as_ptr())
+    }
 }
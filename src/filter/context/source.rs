@@ -1,27 +1,119 @@
+use std::marker::PhantomData;
 use std::ptr;
 
-use super::Context;
+use super::{Context, Parameters, SourceFlags};
 use crate::ffi::*;
 use crate::{Error, Frame};
 
-pub struct Source<'a> {
-    ctx: &'a mut Context<'a>,
+/// Handle to a buffer source filter context, borrowing the graph that owns it.
+///
+/// Handles are obtained with [`Graph::buffer_source`](crate::filter::Graph::buffer_source),
+/// or together with [`Graph::buffers`](crate::filter::Graph::buffers).
+pub struct BufferSource<'a> {
+    ptr: *mut AVFilterContext,
+
+    _marker: PhantomData<&'a mut ()>,
 }
 
-impl<'a> Source<'a> {
-    pub unsafe fn wrap<'b>(ctx: &'b mut Context<'b>) -> Source<'b> {
-        Source { ctx }
+/// Former name of [`BufferSource`].
+pub type Source<'a> = BufferSource<'a>;
+
+impl<'a> BufferSource<'a> {
+    pub unsafe fn wrap<'b>(ctx: &'b mut Context<'b>) -> BufferSource<'b> {
+        BufferSource::from_raw(ctx.as_mut_ptr())
+    }
+
+    /// # Safety
+    ///
+    /// `ptr` must be a valid buffer source context that outlives `'a`.
+    pub unsafe fn from_raw(ptr: *mut AVFilterContext) -> Self {
+        BufferSource {
+            ptr,
+            _marker: PhantomData,
+        }
+    }
+
+    pub unsafe fn as_ptr(&self) -> *const AVFilterContext {
+        self.ptr as *const _
+    }
+
+    pub unsafe fn as_mut_ptr(&mut self) -> *mut AVFilterContext {
+        self.ptr
     }
 }
 
-impl<'a> Source<'a> {
+impl<'a> BufferSource<'a> {
     pub fn failed_requests(&self) -> usize {
-        unsafe { av_buffersrc_get_nb_failed_requests(self.ctx.as_ptr() as *mut _) as usize }
+        unsafe { av_buffersrc_get_nb_failed_requests(self.as_ptr() as *mut _) as usize }
+    }
+
+    /// Configures the source, typically before the graph is validated.
+    pub fn set_parameters(&mut self, value: &Parameters) -> Result<(), Error> {
+        unsafe {
+            let mut raw = av_buffersrc_parameters_alloc();
+
+            if raw.is_null() {
+                return Err(Error::Other {
+                    errno: libc::ENOMEM,
+                });
+            }
+
+            let result = value.fill(raw).and_then(|_| {
+                match av_buffersrc_parameters_set(self.as_mut_ptr(), raw) {
+                    0 => Ok(()),
+                    e => Err(Error::from(e)),
+                }
+            });
+
+            av_channel_layout_uninit(&mut (*raw).ch_layout);
+            av_freep(&mut raw as *mut *mut AVBufferSrcParameters as *mut _);
+
+            result
+        }
     }
 
     pub fn add(&mut self, frame: &Frame) -> Result<(), Error> {
         unsafe {
-            match av_buffersrc_add_frame(self.ctx.as_mut_ptr(), frame.as_ptr() as *mut _) {
+            match av_buffersrc_add_frame(self.as_mut_ptr(), frame.as_ptr() as *mut _) {
+                0 => Ok(()),
+                e => Err(Error::from(e)),
+            }
+        }
+    }
+
+    /// Adds a frame with the given flags. Unless `KEEP_REF` is set, the source
+    /// takes over the references of `frame`, leaving it blank.
+    pub fn add_frame_flags(&mut self, frame: &mut Frame, flags: SourceFlags) -> Result<(), Error> {
+        unsafe {
+            match av_buffersrc_add_frame_flags(self.as_mut_ptr(), frame.as_mut_ptr(), flags.bits())
+            {
+                0 => Ok(()),
+                e => Err(Error::from(e)),
+            }
+        }
+    }
+
+    /// Adds a new reference to `frame`, leaving it untouched. The reference
+    /// is given timestamp `pts` instead of the one of `frame` if set.
+    pub(crate) fn add_ref(&mut self, frame: &Frame, pts: Option<i64>) -> Result<(), Error> {
+        unsafe {
+            if let Some(pts) = pts {
+                let mut copy = Frame::empty();
+
+                match av_frame_ref(copy.as_mut_ptr(), frame.as_ptr()) {
+                    0 => (),
+                    e => return Err(Error::from(e)),
+                }
+
+                copy.set_pts(Some(pts));
+                return self.add_frame_flags(&mut copy, SourceFlags::empty());
+            }
+
+            match av_buffersrc_add_frame_flags(
+                self.as_mut_ptr(),
+                frame.as_ptr() as *mut _,
+                SourceFlags::KEEP_REF.bits(),
+            ) {
                 0 => Ok(()),
                 e => Err(Error::from(e)),
             }
@@ -34,7 +126,7 @@ impl<'a> Source<'a> {
 
     pub fn close(&mut self, pts: i64) -> Result<(), Error> {
         unsafe {
-            match av_buffersrc_close(self.ctx.as_mut_ptr(), pts, 0) {
+            match av_buffersrc_close(self.as_mut_ptr(), pts, 0) {
                 0 => Ok(()),
                 e => Err(Error::from(e)),
             }
@@ -1,8 +1,10 @@
-use libc::c_int;
+use libc::{c_int, c_void};
 use std::marker::PhantomData;
+use std::ptr;
 
 use super::{Flags, Pad};
 use crate::ffi::*;
+use crate::option;
 use crate::utils;
 
 pub struct Filter {
@@ -63,6 +65,46 @@ impl Filter {
     pub fn flags(&self) -> Flags {
         unsafe { Flags::from_bits_truncate((*self.as_ptr()).flags) }
     }
+
+    /// Returns the options of the filter, with their named constants.
+    pub fn options(&self) -> option::Descriptors<'_> {
+        unsafe { option::Descriptors::new((*self.as_ptr()).priv_class) }
+    }
+}
+
+/// Iterator over all filters of the linked libavfilter.
+pub struct FilterIter {
+    opaque: *mut c_void,
+}
+
+impl FilterIter {
+    pub fn new() -> Self {
+        FilterIter {
+            opaque: ptr::null_mut(),
+        }
+    }
+}
+
+impl Default for FilterIter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Iterator for FilterIter {
+    type Item = Filter;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        unsafe {
+            let ptr = av_filter_iterate(&mut self.opaque);
+
+            if ptr.is_null() {
+                None
+            } else {
+                Some(Filter::wrap(ptr as *mut _))
+            }
+        }
+    }
 }
 
 pub struct PadIter<'a> {
@@ -99,4 +141,12 @@ impl<'a> Iterator for PadIter<'a> {
             Some(pad)
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let length = (self.count - self.cur) as usize;
+
+        (length, Some(length))
+    }
 }
+
+impl<'a> ExactSizeIterator for PadIter<'a> {}
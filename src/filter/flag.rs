@@ -9,5 +9,11 @@ bitflags::bitflags! {
         const SUPPORT_TIMELINE_GENERIC  = AVFILTER_FLAG_SUPPORT_TIMELINE_GENERIC;
         const SUPPORT_TIMELINE_INTERNAL = AVFILTER_FLAG_SUPPORT_TIMELINE_INTERNAL;
         const SUPPORT_TIMELINE          = AVFILTER_FLAG_SUPPORT_TIMELINE;
+        /// Only touches frame metadata and side data, never the frame contents.
+        #[cfg(feature = "ffmpeg_6_0")]
+        const METADATA_ONLY             = AVFILTER_FLAG_METADATA_ONLY;
+        /// Requires a hardware device on the filter context.
+        #[cfg(feature = "ffmpeg_7_0")]
+        const HWDEVICE                  = AVFILTER_FLAG_HWDEVICE;
     }
 }
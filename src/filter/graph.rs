@@ -2,11 +2,36 @@ use std::ffi::{CStr, CString};
 use std::ptr::{self, NonNull};
 use std::str::from_utf8_unchecked;
 
-use super::{Context, Filter};
+use super::{command, threading, BufferSink, BufferSource, Context, Filter, Topology};
 use crate::ffi::*;
-use crate::Error;
+use crate::{media, utils, Error};
 use libc::c_int;
 
+#[cfg(feature = "codec")]
+use crate::codec::ParametersRef;
+#[cfg(feature = "codec")]
+use crate::{AsPtr, Rational};
+
+/// A pad left unlinked by [`Graph::parse_open`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct OpenPad {
+    /// Link label from the spec, e.g. `in0` for `[in0]`.
+    pub label: Option<String>,
+    /// Name of the filter instance owning the pad.
+    pub filter: String,
+    pub pad: usize,
+    pub medium: media::Type,
+}
+
+/// The open pads of a parsed graph.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct OpenPads {
+    /// Inputs waiting for a source.
+    pub inputs: Vec<OpenPad>,
+    /// Outputs waiting for a sink.
+    pub outputs: Vec<OpenPad>,
+}
+
 pub struct Graph {
     ptr: *mut AVFilterGraph,
 }
@@ -50,6 +75,55 @@ impl Graph {
         }
     }
 
+    /// Sets the threading of the filters. Must be called before adding any
+    /// filter to the graph.
+    pub fn set_threading(&mut self, config: threading::Config) {
+        unsafe {
+            (*self.as_mut_ptr()).thread_type = config.kind.into();
+            (*self.as_mut_ptr()).nb_threads = config.count as c_int;
+        }
+    }
+
+    pub fn threading(&self) -> threading::Config {
+        unsafe {
+            threading::Config {
+                kind: threading::Type::from((*self.as_ptr()).thread_type),
+                count: (*self.as_ptr()).nb_threads as usize,
+            }
+        }
+    }
+
+    /// Sets the options of the `scale` filters inserted to convert between
+    /// formats, e.g. `flags=bicubic`. Must be called before
+    /// [`validate`](Graph::validate).
+    pub fn set_scale_sws_opts(&mut self, value: &str) -> Result<(), Error> {
+        self.set_option("scale_sws_opts", value)
+    }
+
+    /// Sets the options of the `aresample` filters inserted to convert between
+    /// formats, e.g. `resampler=soxr`. Must be called before
+    /// [`validate`](Graph::validate).
+    pub fn set_aresample_swr_opts(&mut self, value: &str) -> Result<(), Error> {
+        self.set_option("aresample_swr_opts", value)
+    }
+
+    fn set_option(&mut self, name: &str, value: &str) -> Result<(), Error> {
+        unsafe {
+            let name = CString::new(name).unwrap();
+            let value = CString::new(value).unwrap();
+
+            match av_opt_set(
+                self.as_mut_ptr() as *mut _,
+                name.as_ptr(),
+                value.as_ptr(),
+                0,
+            ) {
+                0 => Ok(()),
+                e => Err(Error::from(e)),
+            }
+        }
+    }
+
     pub fn add<'a, 'b>(
         &'a mut self,
         filter: &Filter,
@@ -94,6 +168,90 @@ impl Graph {
         }
     }
 
+    /// Returns a handle to the `buffer` or `abuffer` source named `name`.
+    pub fn buffer_source(&mut self, name: &str) -> Result<BufferSource<'_>, Error> {
+        unsafe {
+            let ptr = self.find_context(name, &["buffer", "abuffer"])?;
+
+            Ok(BufferSource::from_raw(ptr))
+        }
+    }
+
+    /// Returns a handle to the `buffersink` or `abuffersink` named `name`.
+    pub fn buffer_sink(&mut self, name: &str) -> Result<BufferSink<'_>, Error> {
+        unsafe {
+            let ptr = self.find_context(name, &["buffersink", "abuffersink"])?;
+
+            Ok(BufferSink::from_raw(ptr))
+        }
+    }
+
+    /// Returns handles to the sources named `sources` and the sinks named
+    /// `sinks` at once, in the same order, for feeding and draining several
+    /// endpoints of the graph in the same scope.
+    ///
+    /// Fails with `EINVAL` if a name is listed twice, as two handles must not
+    /// refer to the same context.
+    pub fn buffers(
+        &mut self,
+        sources: &[&str],
+        sinks: &[&str],
+    ) -> Result<(Vec<BufferSource<'_>>, Vec<BufferSink<'_>>), Error> {
+        let names = sources.iter().chain(sinks);
+
+        if names
+            .clone()
+            .enumerate()
+            .any(|(i, name)| names.clone().skip(i + 1).any(|other| other == name))
+        {
+            return Err(Error::Other {
+                errno: libc::EINVAL,
+            });
+        }
+
+        unsafe {
+            let sources = sources
+                .iter()
+                .map(|name| {
+                    self.find_context(name, &["buffer", "abuffer"])
+                        .map(|ptr| BufferSource::from_raw(ptr))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let sinks = sinks
+                .iter()
+                .map(|name| {
+                    self.find_context(name, &["buffersink", "abuffersink"])
+                        .map(|ptr| BufferSink::from_raw(ptr))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Ok((sources, sinks))
+        }
+    }
+
+    /// Looks up the filter context `name`, checking that it is an instance of
+    /// one of `filters`.
+    unsafe fn find_context(
+        &mut self,
+        name: &str,
+        filters: &[&str],
+    ) -> Result<*mut AVFilterContext, Error> {
+        let name = CString::new(name).unwrap();
+        let ptr = avfilter_graph_get_filter(self.as_mut_ptr(), name.as_ptr());
+
+        if ptr.is_null() {
+            return Err(Error::FilterNotFound);
+        }
+
+        if !filters.contains(&utils::str_from_c_ptr((*(*ptr).filter).name)) {
+            return Err(Error::Other {
+                errno: libc::EINVAL,
+            });
+        }
+
+        Ok(ptr)
+    }
+
     pub fn dump(&self) -> String {
         unsafe {
             let ptr = avfilter_graph_dump(self.as_ptr() as *mut _, ptr::null());
@@ -106,6 +264,76 @@ impl Graph {
         }
     }
 
+    /// Returns the filter instances and links of the graph. Link formats are
+    /// only known once the graph is validated.
+    pub fn topology(&self) -> Topology {
+        unsafe { Topology::from_raw(self.as_ptr()) }
+    }
+
+    /// Sends a command to the filters matching `target` and returns the
+    /// response of the last one that handled it.
+    ///
+    /// `target` is a filter instance name, a filter name such as `volume`, or
+    /// [`command::ALL`].
+    pub fn send_command(
+        &mut self,
+        target: &str,
+        cmd: &str,
+        arg: &str,
+        flags: command::Flags,
+    ) -> Result<String, Error> {
+        unsafe {
+            let target = CString::new(target).unwrap();
+            let cmd = CString::new(cmd).unwrap();
+            let arg = CString::new(arg).unwrap();
+            let mut response = [0u8; command::RESPONSE_SIZE];
+
+            match avfilter_graph_send_command(
+                self.as_mut_ptr(),
+                target.as_ptr(),
+                cmd.as_ptr(),
+                arg.as_ptr(),
+                response.as_mut_ptr() as *mut _,
+                response.len() as c_int,
+                flags.bits(),
+            ) {
+                n if n >= 0 => Ok(command::response(&response)),
+                e => Err(Error::from(e)),
+            }
+        }
+    }
+
+    /// Queues a command to run on the filters matching `target` when they
+    /// process the first frame at or after `ts`, in seconds.
+    ///
+    /// See [`send_command`](Graph::send_command) for the target syntax.
+    pub fn queue_command(
+        &mut self,
+        target: &str,
+        cmd: &str,
+        arg: &str,
+        flags: command::Flags,
+        ts: f64,
+    ) -> Result<(), Error> {
+        unsafe {
+            let target = CString::new(target).unwrap();
+            let cmd = CString::new(cmd).unwrap();
+            let arg = CString::new(arg).unwrap();
+
+            match avfilter_graph_queue_command(
+                self.as_mut_ptr(),
+                target.as_ptr(),
+                cmd.as_ptr(),
+                arg.as_ptr(),
+                flags.bits(),
+                ts,
+            ) {
+                n if n >= 0 => Ok(()),
+                e => Err(Error::from(e)),
+            }
+        }
+    }
+
     pub fn input(&mut self, name: &str, pad: usize) -> Result<Parser<'_>, Error> {
         Parser::new(self).input(name, pad)
     }
@@ -117,6 +345,177 @@ impl Graph {
     pub fn parse(&mut self, spec: &str) -> Result<(), Error> {
         Parser::new(self).parse(spec)
     }
+
+    /// Parses `spec` without pre-created endpoints, returning the pads it
+    /// leaves unlinked, such as `in0`, `in1` and `out` in
+    /// `[in0][in1]overlay,scale=640:-2[out]`.
+    pub fn parse_open(&mut self, spec: &str) -> Result<OpenPads, Error> {
+        unsafe {
+            let spec = CString::new(spec).unwrap();
+            let mut inputs = ptr::null_mut();
+            let mut outputs = ptr::null_mut();
+
+            let result =
+                avfilter_graph_parse2(self.as_mut_ptr(), spec.as_ptr(), &mut inputs, &mut outputs);
+
+            let pads = OpenPads {
+                inputs: open_pads(inputs, true),
+                outputs: open_pads(outputs, false),
+            };
+
+            avfilter_inout_free(&mut inputs);
+            avfilter_inout_free(&mut outputs);
+
+            match result {
+                n if n >= 0 => Ok(pads),
+                e => Err(Error::from(e)),
+            }
+        }
+    }
+
+    /// Links output `src_pad` of filter `src` to input `dst_pad` of filter `dst`.
+    pub fn link(
+        &mut self,
+        src: &str,
+        src_pad: usize,
+        dst: &str,
+        dst_pad: usize,
+    ) -> Result<(), Error> {
+        unsafe {
+            let src = self.get(src).ok_or(Error::FilterNotFound)?.as_mut_ptr();
+            let dst = self.get(dst).ok_or(Error::FilterNotFound)?.as_mut_ptr();
+
+            match avfilter_link(src, src_pad as _, dst, dst_pad as _) {
+                0 => Ok(()),
+                e => Err(Error::from(e)),
+            }
+        }
+    }
+
+    /// Creates a `buffer` or `abuffer` source for frames described by
+    /// `parameters` with timestamps in `time_base`, and links it to the open
+    /// input `pad`. The source is named after the pad label if it has one.
+    #[cfg(feature = "codec")]
+    pub fn add_source<P: AsPtr<AVCodecParameters>>(
+        &mut self,
+        pad: &OpenPad,
+        parameters: &P,
+        time_base: Rational,
+    ) -> Result<Context<'_>, Error> {
+        let parameters =
+            unsafe { ParametersRef::from_raw(parameters.as_ptr()) }.ok_or(Error::InvalidData)?;
+        let (filter, args) = source_args(&parameters, time_base)?;
+        let name = pad
+            .label
+            .clone()
+            .unwrap_or_else(|| format!("src_{}_{}", pad.filter, pad.pad));
+
+        self.add(
+            &super::find(filter).ok_or(Error::FilterNotFound)?,
+            &name,
+            &args,
+        )?;
+        self.link(&name, 0, &pad.filter, pad.pad)?;
+
+        Ok(self.get(&name).unwrap())
+    }
+
+    /// Creates a `buffersink` or `abuffersink` and links the open output `pad`
+    /// to it. The sink is named after the pad label if it has one.
+    pub fn add_sink(&mut self, pad: &OpenPad) -> Result<Context<'_>, Error> {
+        let filter = match pad.medium {
+            media::Type::Video => "buffersink",
+            media::Type::Audio => "abuffersink",
+            _ => {
+                return Err(Error::Other {
+                    errno: libc::EINVAL,
+                })
+            }
+        };
+        let name = pad
+            .label
+            .clone()
+            .unwrap_or_else(|| format!("sink_{}_{}", pad.filter, pad.pad));
+
+        self.add(
+            &super::find(filter).ok_or(Error::FilterNotFound)?,
+            &name,
+            "",
+        )?;
+        self.link(&pad.filter, pad.pad, &name, 0)?;
+
+        Ok(self.get(&name).unwrap())
+    }
+}
+
+/// Collects a list of open pads, `inputs` telling whether they are filter inputs.
+unsafe fn open_pads(mut inout: *const AVFilterInOut, inputs: bool) -> Vec<OpenPad> {
+    let mut pads = Vec::new();
+
+    while !inout.is_null() {
+        let context = (*inout).filter_ctx;
+        let pad = (*inout).pad_idx;
+        let list = if inputs {
+            (*context).input_pads
+        } else {
+            (*context).output_pads
+        };
+
+        pads.push(OpenPad {
+            label: utils::optional_str_from_c_ptr((*inout).name).map(String::from),
+            filter: utils::str_from_c_ptr((*context).name).to_owned(),
+            pad: pad as usize,
+            medium: media::Type::from(avfilter_pad_get_type(list, pad)),
+        });
+
+        inout = (*inout).next;
+    }
+
+    pads
+}
+
+/// Returns the buffer source filter name and arguments for the given stream.
+#[cfg(feature = "codec")]
+fn source_args(
+    parameters: &ParametersRef<'_>,
+    time_base: Rational,
+) -> Result<(&'static str, String), Error> {
+    let format = unsafe { (*parameters.as_ptr()).format };
+
+    match parameters.medium() {
+        media::Type::Video => {
+            let args = format!(
+                "video_size={}x{}:pix_fmt={format}:time_base={time_base}:pixel_aspect={}",
+                parameters.width(),
+                parameters.height(),
+                parameters.sample_aspect_ratio(),
+            );
+
+            // codec parameters only carry the frame rate since 6.1
+            #[cfg(feature = "ffmpeg_6_1")]
+            let args = match parameters.framerate() {
+                rate if rate.numerator() > 0 && rate.denominator() > 0 => {
+                    format!("{args}:frame_rate={rate}")
+                }
+                _ => args,
+            };
+
+            Ok(("buffer", args))
+        }
+
+        media::Type::Audio => Ok((
+            "abuffer",
+            format!(
+                "time_base={time_base}:sample_rate={}:sample_fmt={format}:channel_layout={}",
+                parameters.sample_rate(),
+                parameters.ch_layout().description(),
+            ),
+        )),
+
+        _ => Err(Error::Other {
+            errno: libc::EINVAL,
+        }),
+    }
 }
 
 impl Drop for Graph {
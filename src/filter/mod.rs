@@ -5,13 +5,24 @@ pub mod pad;
 pub use self::pad::Pad;
 
 pub mod filter;
-pub use self::filter::Filter;
+pub use self::filter::{Filter, FilterIter};
 
 pub mod context;
-pub use self::context::{Context, Sink, Source};
+pub use self::context::{BufferSink, BufferSource, Context, Sink, Source};
 
 pub mod graph;
-pub use self::graph::Graph;
+pub use self::graph::{Graph, OpenPad, OpenPads};
+
+pub mod analysis;
+
+pub mod command;
+
+pub mod quality;
+
+pub mod threading;
+
+pub mod topology;
+pub use self::topology::{Link, Node, Topology};
 
 use std::ffi::CString;
 
@@ -30,6 +41,10 @@ pub fn license() -> &'static str {
     unsafe { utils::str_from_c_ptr(avfilter_license()) }
 }
 
+pub fn list() -> FilterIter {
+    FilterIter::new()
+}
+
 pub fn find(name: &str) -> Option<Filter> {
     unsafe {
         let name = CString::new(name).unwrap();
@@ -45,7 +60,9 @@ pub fn find(name: &str) -> Option<Filter> {
 
 #[cfg(test)]
 mod tests {
+    use super::context::{SinkFlags, SourceFlags};
     use super::*;
+    use crate::option;
 
     #[test]
     fn test_paditer() {
@@ -59,4 +76,333 @@ mod tests {
             vec!("main", "overlay")
         );
     }
+
+    #[test]
+    fn test_parse_open() {
+        let mut graph = Graph::new();
+        let pads = graph
+            .parse_open("[in0][in1]overlay,scale=640:-2[out]")
+            .unwrap();
+
+        let labels = |pads: &[OpenPad]| {
+            pads.iter()
+                .map(|pad| pad.label.clone().unwrap())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(labels(&pads.inputs), vec!["in0", "in1"]);
+        assert_eq!(labels(&pads.outputs), vec!["out"]);
+        assert_eq!(pads.inputs[1].pad, 1);
+        assert!(pads
+            .inputs
+            .iter()
+            .chain(&pads.outputs)
+            .all(|pad| pad.medium == crate::media::Type::Video));
+
+        let sink = pads.outputs[0].clone();
+        graph.add_sink(&sink).unwrap();
+        assert!(graph.get("out").is_some());
+    }
+
+    #[test]
+    fn test_handles() {
+        let mut graph = Graph::new();
+        let pads = graph.parse_open("[in]null[out]").unwrap();
+
+        graph
+            .add(
+                &find("buffer").unwrap(),
+                "in",
+                "video_size=16x16:pix_fmt=yuv420p:time_base=1/25",
+            )
+            .unwrap();
+        graph.link("in", 0, &pads.inputs[0].filter, 0).unwrap();
+        graph.add_sink(&pads.outputs[0]).unwrap();
+        graph.validate().unwrap();
+
+        assert!(graph.buffer_sink("in").is_err());
+        assert!(graph.buffers(&["out"], &["in"]).is_err());
+        assert!(graph.buffers(&["in", "in"], &["out"]).is_err());
+
+        let (mut sources, mut sinks) = graph.buffers(&["in"], &["out"]).unwrap();
+        let (source, sink) = (&mut sources[0], &mut sinks[0]);
+        assert_eq!((sink.width(), sink.height()), (16, 16));
+
+        for pts in 0..3 {
+            let mut frame = crate::frame::Video::new(crate::format::Pixel::YUV420P, 16, 16);
+            frame.set_pts(Some(pts));
+            source.add(&frame).unwrap();
+
+            let mut output = crate::frame::Video::empty();
+            sink.frame(&mut output).unwrap();
+            assert_eq!(output.pts(), Some(pts));
+        }
+    }
+
+    /// Builds a graph of `filters`, given as filter, instance name and
+    /// arguments, linking each one to the next.
+    fn chain(filters: &[(&str, &str, &str)]) -> Graph {
+        let mut graph = Graph::new();
+
+        for (filter, name, args) in filters {
+            graph.add(&find(filter).unwrap(), name, args).unwrap();
+        }
+        for pair in filters.windows(2) {
+            graph.link(pair[0].1, 0, pair[1].1, 0).unwrap();
+        }
+
+        graph
+    }
+
+    #[test]
+    fn test_flags() {
+        let mut graph = chain(&[
+            (
+                "buffer",
+                "in",
+                "video_size=16x16:pix_fmt=yuv420p:time_base=1/25",
+            ),
+            ("buffersink", "out", ""),
+        ]);
+        graph.validate().unwrap();
+
+        let (mut sources, mut sinks) = graph.buffers(&["in"], &["out"]).unwrap();
+        let (source, sink) = (&mut sources[0], &mut sinks[0]);
+
+        let mut output = crate::frame::Video::empty();
+        assert_eq!(
+            sink.get_frame_flags(&mut output, SinkFlags::NO_REQUEST),
+            Err(crate::Error::Other {
+                errno: libc::EAGAIN
+            })
+        );
+
+        let mut frame = crate::frame::Video::new(crate::format::Pixel::YUV420P, 16, 16);
+        frame.set_pts(Some(0));
+        source
+            .add_frame_flags(&mut frame, SourceFlags::KEEP_REF)
+            .unwrap();
+        assert_eq!((frame.width(), frame.pts()), (16, Some(0)));
+
+        frame.set_pts(Some(1));
+        source
+            .add_frame_flags(&mut frame, SourceFlags::empty())
+            .unwrap();
+        assert_eq!((frame.width(), frame.pts()), (0, None));
+
+        for _ in 0..2 {
+            let mut peeked = crate::frame::Video::empty();
+            sink.get_frame_flags(&mut peeked, SinkFlags::PEEK).unwrap();
+            assert_eq!(peeked.pts(), Some(0));
+        }
+
+        for pts in 0..2 {
+            let mut output = crate::frame::Video::empty();
+            sink.get_frame_flags(&mut output, SinkFlags::empty())
+                .unwrap();
+            assert_eq!(output.pts(), Some(pts));
+        }
+    }
+
+    /// Allocates an instance of `filter` without initializing it, leaving room
+    /// to set source parameters first.
+    unsafe fn alloc(graph: &mut Graph, filter: &str, name: &str) -> BufferSource<'static> {
+        let name = CString::new(name).unwrap();
+        let ptr = avfilter_graph_alloc_filter(
+            graph.as_mut_ptr(),
+            find(filter).unwrap().as_ptr(),
+            name.as_ptr(),
+        );
+        assert!(!ptr.is_null());
+
+        BufferSource::from_raw(ptr)
+    }
+
+    #[test]
+    fn test_parameters() {
+        use crate::{format, ChannelLayout, Rational};
+
+        let mut graph = Graph::new();
+        let mut parameters =
+            context::Parameters::video(format::Pixel::YUV420P, 32, 16, Rational(1, 30));
+        parameters.aspect_ratio = Some(Rational(4, 3));
+
+        unsafe {
+            let mut source = alloc(&mut graph, "buffer", "in");
+            source.set_parameters(&parameters).unwrap();
+            assert_eq!(avfilter_init_str(source.as_mut_ptr(), std::ptr::null()), 0);
+        }
+        graph.add(&find("buffersink").unwrap(), "out", "").unwrap();
+        graph.link("in", 0, "out", 0).unwrap();
+        graph.validate().unwrap();
+
+        let sink = graph.buffer_sink("out").unwrap();
+        assert_eq!(sink.pixel_format(), format::Pixel::YUV420P);
+        assert_eq!((sink.width(), sink.height()), (32, 16));
+        assert_eq!(sink.aspect_ratio(), Rational(4, 3));
+        assert_eq!(sink.time_base(), Rational(1, 30));
+
+        let mut graph = Graph::new();
+        let parameters = context::Parameters::audio(
+            format::Sample::F32(format::sample::Type::Planar),
+            48000,
+            ChannelLayout::STEREO,
+            Rational(1, 48000),
+        );
+
+        unsafe {
+            let mut source = alloc(&mut graph, "abuffer", "in");
+            source.set_parameters(&parameters).unwrap();
+            assert_eq!(avfilter_init_str(source.as_mut_ptr(), std::ptr::null()), 0);
+        }
+        graph.add(&find("abuffersink").unwrap(), "out", "").unwrap();
+        graph.link("in", 0, "out", 0).unwrap();
+        graph.validate().unwrap();
+
+        let sink = graph.buffer_sink("out").unwrap();
+        assert_eq!(sink.sample_rate(), 48000);
+        assert_eq!(sink.ch_layout().unwrap(), ChannelLayout::STEREO);
+        assert_eq!(sink.time_base(), Rational(1, 48000));
+    }
+
+    #[test]
+    fn test_commands() {
+        use crate::format::{sample, Sample};
+        use crate::frame::Audio;
+        use crate::ChannelLayoutMask;
+
+        let mut graph = chain(&[
+            (
+                "abuffer",
+                "in",
+                "time_base=1/8000:sample_rate=8000:sample_fmt=flt:channel_layout=mono",
+            ),
+            ("volume", "gain", "volume=0.5"),
+            ("abuffersink", "out", ""),
+        ]);
+        graph.validate().unwrap();
+
+        let flags = command::Flags::empty();
+        assert!(graph.send_command("gain", "volume", "0.5", flags).is_ok());
+        assert!(graph.send_command("volume", "volume", "0.5", flags).is_ok());
+        assert!(graph
+            .get("gain")
+            .unwrap()
+            .send_command("volume", "0.5", flags)
+            .is_ok());
+        assert!(graph
+            .send_command("missing", "volume", "0.5", flags)
+            .is_err());
+
+        // halve the volume again from 0.2 seconds on
+        graph
+            .queue_command("gain", "volume", "0.25", flags, 0.2)
+            .unwrap();
+
+        let (mut sources, mut sinks) = graph.buffers(&["in"], &["out"]).unwrap();
+        let mut levels = Vec::new();
+
+        for pts in (0..4).map(|i| i * 800) {
+            let mut frame = Audio::new(
+                Sample::F32(sample::Type::Packed),
+                800,
+                ChannelLayoutMask::MONO,
+            );
+            frame.set_rate(8000);
+            frame.set_pts(Some(pts));
+            frame.plane_mut::<f32>(0).fill(1.0);
+            sources[0].add(&frame).unwrap();
+
+            loop {
+                let mut output = Audio::empty();
+                if sinks[0].frame(&mut output).is_err() {
+                    break;
+                }
+
+                levels.push((output.pts(), output.plane::<f32>(0)[0]));
+            }
+        }
+
+        assert_eq!(
+            levels,
+            vec![
+                (Some(0), 0.5),
+                (Some(800), 0.5),
+                (Some(1600), 0.25),
+                (Some(2400), 0.25),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_topology() {
+        let mut graph = Graph::new();
+        graph
+            .add(
+                &find("buffer").unwrap(),
+                "in",
+                "video_size=16x16:pix_fmt=yuv420p:time_base=1/25",
+            )
+            .unwrap();
+        graph.add(&find("null").unwrap(), "pass", "").unwrap();
+        graph.add(&find("buffersink").unwrap(), "out", "").unwrap();
+        graph.link("in", 0, "pass", 0).unwrap();
+        graph.link("pass", 0, "out", 0).unwrap();
+        graph.validate().unwrap();
+
+        let topology = graph.topology();
+        let names = topology.nodes.iter().map(|n| n.name.as_str());
+        assert!(names.clone().any(|n| n == "pass"));
+        assert!(names.clone().any(|n| n == "out"));
+
+        let link = topology.links.iter().find(|l| l.src == "in").unwrap();
+        assert_eq!(link.dst, "pass");
+        assert_eq!((link.src_pad, link.dst_pad), (0, 0));
+        assert_eq!(link.medium, crate::media::Type::Video);
+        assert_eq!(
+            link.format,
+            Some(context::Format::Pixel(crate::format::Pixel::YUV420P))
+        );
+        assert_eq!((link.width, link.height), (16, 16));
+
+        let dot = topology.to_dot();
+        assert!(dot.starts_with("digraph {"));
+        assert!(dot.contains("\"in\" -> \"pass\" [label=\"yuv420p 16x16 1/25\"];"));
+    }
+
+    #[test]
+    fn test_settings() {
+        let mut graph = Graph::new();
+        let config = threading::Config {
+            kind: threading::Type::Slice,
+            count: 2,
+        };
+        graph.set_threading(config);
+        assert_eq!(graph.threading(), config);
+
+        graph.set_scale_sws_opts("flags=bicubic").unwrap();
+        graph.set_aresample_swr_opts("resampler=swr").unwrap();
+        assert_eq!(
+            unsafe { utils::optional_str_from_c_ptr((*graph.as_ptr()).scale_sws_opts) },
+            Some("flags=bicubic")
+        );
+    }
+
+    #[test]
+    fn test_list() {
+        let filters = list()
+            .map(|filter| filter.name().to_string())
+            .collect::<Vec<_>>();
+        assert!(filters.iter().any(|name| name == "buffer"));
+        assert!(filters.iter().any(|name| name == "overlay"));
+
+        assert!(find("concat")
+            .unwrap()
+            .flags()
+            .contains(Flags::DYNAMIC_INPUTS | Flags::DYNAMIC_OUTPUTS));
+
+        let scale = find("scale").unwrap();
+        let width = scale.options().find(|o| o.name() == "w").unwrap();
+        assert_eq!(width.kind(), option::Type::STRING);
+        assert!(width.flags().contains(option::Flags::FILTERING_PARAM));
+    }
 }
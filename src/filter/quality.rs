@@ -0,0 +1,546 @@
+//! Objective quality metrics between a reference and a distorted video stream.
+//!
+//! A [`Comparator`] feeds pairs of frames into a graph running the `psnr`,
+//! `ssim` and, when built with `build-lib-vmaf`, `libvmaf` filters. The
+//! distorted frames are scaled to the size of the reference ones, and both are
+//! converted to a common pixel format.
+
+#[cfg(feature = "build-lib-vmaf")]
+use std::path::PathBuf;
+
+use super::Graph;
+use crate::ffi::*;
+use crate::{format, frame, DictionaryRef, Error, Frame};
+
+/// Pixel formats the metrics are computed in, with their bit depth. Other
+/// formats are compared as `yuv420p`.
+const FORMATS: [(format::Pixel, u32); 6] = [
+    (format::Pixel::YUV420P, 8),
+    (format::Pixel::YUV422P, 8),
+    (format::Pixel::YUV444P, 8),
+    (format::Pixel::YUV420P10LE, 10),
+    (format::Pixel::YUV422P10LE, 10),
+    (format::Pixel::YUV444P10LE, 10),
+];
+
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum Metric {
+    Psnr,
+    Ssim,
+    #[cfg(feature = "build-lib-vmaf")]
+    Vmaf,
+}
+
+impl Metric {
+    pub fn filter_name(self) -> &'static str {
+        match self {
+            Metric::Psnr => "psnr",
+            Metric::Ssim => "ssim",
+            #[cfg(feature = "build-lib-vmaf")]
+            Metric::Vmaf => "libvmaf",
+        }
+    }
+}
+
+/// Peak signal-to-noise ratio per plane, in dB. Identical planes score
+/// infinity.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct Psnr {
+    pub y: f64,
+    pub u: f64,
+    pub v: f64,
+    /// Over all planes, weighted by their size.
+    pub average: f64,
+}
+
+/// Structural similarity index per plane, in `0..=1`.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct Ssim {
+    pub y: f64,
+    pub u: f64,
+    pub v: f64,
+    /// Over all planes, weighted by their size.
+    pub all: f64,
+}
+
+/// Scores of one pair of frames.
+#[derive(PartialEq, Clone, Copy, Debug, Default)]
+pub struct Score {
+    /// Position of the pair in the streams.
+    pub index: usize,
+    pub psnr: Option<Psnr>,
+    pub ssim: Option<Ssim>,
+    /// VMAF score, in `0..=100`.
+    pub vmaf: Option<f64>,
+}
+
+/// Scores of every pair of frames, and over the whole streams.
+#[derive(PartialEq, Clone, Debug, Default)]
+pub struct Summary {
+    pub frames: Vec<Score>,
+    /// Computed from the mean squared error over all frames.
+    pub psnr: Option<Psnr>,
+    /// Mean of the frame scores.
+    pub ssim: Option<Ssim>,
+    /// Mean of the frame scores.
+    pub vmaf: Option<f64>,
+}
+
+/// Computes quality metrics between two streams of video frames.
+///
+/// All the frames of a stream must share the format and size of its first
+/// frame. Pairs are matched by the order they are sent in, regardless of
+/// their timestamps.
+pub struct Comparator {
+    metrics: Vec<Metric>,
+    graph: Option<Graph>,
+    /// Bit depth the frames are compared at.
+    depth: u32,
+    sent: usize,
+
+    frame: Frame,
+    frames: Vec<Score>,
+    /// Mean squared error per plane and over all planes, for each frame.
+    errors: Vec<[f64; 4]>,
+
+    /// `libvmaf` log, created when the graph is built.
+    #[cfg(feature = "build-lib-vmaf")]
+    log_path: Option<PathBuf>,
+}
+
+impl Comparator {
+    /// Computes each of `metrics`, once however many times it is listed.
+    pub fn new(metrics: &[Metric]) -> Self {
+        let mut unique = Vec::new();
+        for metric in metrics {
+            if !unique.contains(metric) {
+                unique.push(*metric);
+            }
+        }
+
+        Comparator {
+            metrics: unique,
+            graph: None,
+            depth: 8,
+            sent: 0,
+            frame: unsafe { Frame::empty() },
+            frames: Vec::new(),
+            errors: Vec::new(),
+            #[cfg(feature = "build-lib-vmaf")]
+            log_path: None,
+        }
+    }
+
+    /// Compares `distorted` with `reference`.
+    pub fn send(
+        &mut self,
+        reference: &frame::Video,
+        distorted: &frame::Video,
+    ) -> Result<(), Error> {
+        if self.graph.is_none() {
+            self.graph = Some(self.build(reference, distorted)?);
+        }
+
+        let pts = self.sent as i64;
+        let graph = self.graph.as_mut().unwrap();
+
+        let (mut sources, _) = graph.buffers(&["reference", "distorted"], &[])?;
+        sources[0].add_ref(reference, Some(pts))?;
+        sources[1].add_ref(distorted, Some(pts))?;
+
+        self.sent += 1;
+        self.drain()
+    }
+
+    /// Ends both streams and returns the scores.
+    pub fn finish(mut self) -> Result<Summary, Error> {
+        if let Some(graph) = &mut self.graph {
+            let (mut sources, _) = graph.buffers(&["reference", "distorted"], &[])?;
+            sources[0].flush()?;
+            sources[1].flush()?;
+            self.drain()?;
+        }
+
+        // libvmaf writes its log when freed
+        self.graph = None;
+
+        #[cfg(feature = "build-lib-vmaf")]
+        if let Some(log_path) = &self.log_path {
+            let log = std::fs::read_to_string(log_path).map_err(|_| Error::InvalidData)?;
+
+            for (index, vmaf) in vmaf_scores(&log).into_iter().enumerate() {
+                self.score(index).vmaf = Some(vmaf);
+            }
+        }
+
+        let mean = |value: &dyn Fn(&Score) -> Option<f64>| {
+            let values = self.frames.iter().map(value).collect::<Option<Vec<_>>>()?;
+
+            (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+        };
+
+        let psnr = if self.errors.is_empty() {
+            None
+        } else {
+            let error = |plane: usize| {
+                self.errors.iter().map(|e| e[plane]).sum::<f64>() / self.errors.len() as f64
+            };
+            let peak = f64::from((1 << self.depth) - 1);
+            let psnr = |plane: usize| 10.0 * (peak * peak / error(plane)).log10();
+
+            Some(Psnr {
+                y: psnr(0),
+                u: psnr(1),
+                v: psnr(2),
+                average: psnr(3),
+            })
+        };
+
+        let ssim = mean(&|s| s.ssim.map(|s| s.y)).and_then(|y| {
+            Some(Ssim {
+                y,
+                u: mean(&|s| s.ssim.map(|s| s.u))?,
+                v: mean(&|s| s.ssim.map(|s| s.v))?,
+                all: mean(&|s| s.ssim.map(|s| s.all))?,
+            })
+        });
+        let vmaf = mean(&|s| s.vmaf);
+
+        Ok(Summary {
+            frames: std::mem::take(&mut self.frames),
+            psnr,
+            ssim,
+            vmaf,
+        })
+    }
+
+    fn build(
+        &mut self,
+        reference: &frame::Video,
+        distorted: &frame::Video,
+    ) -> Result<Graph, Error> {
+        if self.metrics.is_empty() {
+            return Err(Error::Other {
+                errno: libc::EINVAL,
+            });
+        }
+
+        let (format, depth) = FORMATS
+            .into_iter()
+            .find(|(format, _)| *format == reference.format())
+            .unwrap_or(FORMATS[0]);
+        let format_args = format!(
+            "pix_fmts={}",
+            format.descriptor().ok_or(Error::InvalidData)?.name()
+        );
+        let outputs = self.metrics.len().to_string();
+
+        self.depth = depth;
+
+        #[cfg(feature = "build-lib-vmaf")]
+        if self.metrics.contains(&Metric::Vmaf) && self.log_path.is_none() {
+            self.log_path = Some(create_log()?);
+        }
+
+        let mut graph = Graph::new();
+
+        chain(
+            &mut graph,
+            &[
+                ("buffer", "reference", &source_args(reference)),
+                ("format", "reference_format", &format_args),
+                ("split", "reference_split", &outputs),
+            ],
+        )?;
+        chain(
+            &mut graph,
+            &[
+                ("buffer", "distorted", &source_args(distorted)),
+                (
+                    "scale",
+                    "distorted_scale",
+                    &format!(
+                        "w={}:h={}:flags=bicubic",
+                        reference.width(),
+                        reference.height()
+                    ),
+                ),
+                ("format", "distorted_format", &format_args),
+                ("split", "distorted_split", &outputs),
+            ],
+        )?;
+
+        for (index, metric) in self.metrics.iter().enumerate() {
+            let name = metric.filter_name();
+            #[cfg(feature = "build-lib-vmaf")]
+            let args = match (metric, &self.log_path) {
+                (Metric::Vmaf, Some(log_path)) => format!(
+                    "log_fmt=csv:log_path='{}'",
+                    log_path.to_string_lossy().replace('\'', "'\\''")
+                ),
+                _ => String::new(),
+            };
+            #[cfg(not(feature = "build-lib-vmaf"))]
+            let args = String::new();
+            let sink = format!("{name}_out");
+
+            chain(
+                &mut graph,
+                &[(name, name, &args), ("buffersink", &sink, "")],
+            )?;
+            graph.link("distorted_split", index, name, 0)?;
+            graph.link("reference_split", index, name, 1)?;
+        }
+
+        graph.validate()?;
+
+        Ok(graph)
+    }
+
+    fn drain(&mut self) -> Result<(), Error> {
+        let Some(graph) = &mut self.graph else {
+            return Ok(());
+        };
+
+        let names = self
+            .metrics
+            .iter()
+            .map(|metric| format!("{}_out", metric.filter_name()))
+            .collect::<Vec<_>>();
+        let (_, sinks) =
+            graph.buffers(&[], &names.iter().map(String::as_str).collect::<Vec<_>>())?;
+
+        for (metric, mut sink) in self.metrics.iter().zip(sinks) {
+            let mut index = match metric {
+                Metric::Psnr => self.errors.len(),
+                Metric::Ssim => self.frames.iter().filter(|s| s.ssim.is_some()).count(),
+                #[cfg(feature = "build-lib-vmaf")]
+                Metric::Vmaf => 0,
+            };
+
+            loop {
+                match sink.frame(&mut self.frame) {
+                    Ok(()) => (),
+                    Err(Error::Eof) => break,
+                    Err(Error::Other { errno }) if errno == libc::EAGAIN => break,
+                    Err(e) => return Err(e),
+                }
+
+                let metadata = self.frame.metadata();
+
+                match metric {
+                    Metric::Psnr => {
+                        let (psnr, errors) = psnr(&metadata).ok_or(Error::InvalidData)?;
+
+                        self.errors.push(errors);
+                        score(&mut self.frames, index).psnr = Some(psnr);
+                    }
+                    Metric::Ssim => {
+                        score(&mut self.frames, index).ssim =
+                            Some(ssim(&metadata).ok_or(Error::InvalidData)?);
+                    }
+                    // scores are read from the log
+                    #[cfg(feature = "build-lib-vmaf")]
+                    Metric::Vmaf => (),
+                }
+
+                index += 1;
+
+                unsafe {
+                    av_frame_unref(self.frame.as_mut_ptr());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "build-lib-vmaf")]
+    fn score(&mut self, index: usize) -> &mut Score {
+        score(&mut self.frames, index)
+    }
+}
+
+#[cfg(feature = "build-lib-vmaf")]
+impl Drop for Comparator {
+    fn drop(&mut self) {
+        self.graph = None;
+
+        if let Some(log_path) = &self.log_path {
+            let _ = std::fs::remove_file(log_path);
+        }
+    }
+}
+
+/// Returns the score at `index`, adding the missing ones.
+fn score(frames: &mut Vec<Score>, index: usize) -> &mut Score {
+    while frames.len() <= index {
+        frames.push(Score {
+            index: frames.len(),
+            ..Default::default()
+        });
+    }
+
+    &mut frames[index]
+}
+
+fn source_args(frame: &frame::Video) -> String {
+    format!(
+        "video_size={}x{}:pix_fmt={}:time_base=1/1",
+        frame.width(),
+        frame.height(),
+        AVPixelFormat::from(frame.format()).0,
+    )
+}
+
+/// Adds `filters`, given as filter, instance name and arguments, linking each
+/// one to the next.
+fn chain(graph: &mut Graph, filters: &[(&str, &str, &str)]) -> Result<(), Error> {
+    for (filter, name, args) in filters {
+        graph.add(
+            &super::find(filter).ok_or(Error::FilterNotFound)?,
+            name,
+            args,
+        )?;
+    }
+
+    for pair in filters.windows(2) {
+        graph.link(pair[0].1, 0, pair[1].1, 0)?;
+    }
+
+    Ok(())
+}
+
+fn parse(metadata: &DictionaryRef, key: &str) -> Option<f64> {
+    metadata.get(key)?.trim().parse().ok()
+}
+
+/// Reads the scores and mean squared errors set by `psnr`.
+fn psnr(metadata: &DictionaryRef) -> Option<(Psnr, [f64; 4])> {
+    let psnr = Psnr {
+        y: parse(metadata, "lavfi.psnr.psnr.y")?,
+        u: parse(metadata, "lavfi.psnr.psnr.u")?,
+        v: parse(metadata, "lavfi.psnr.psnr.v")?,
+        average: parse(metadata, "lavfi.psnr.psnr_avg")?,
+    };
+    let errors = [
+        parse(metadata, "lavfi.psnr.mse.y")?,
+        parse(metadata, "lavfi.psnr.mse.u")?,
+        parse(metadata, "lavfi.psnr.mse.v")?,
+        parse(metadata, "lavfi.psnr.mse_avg")?,
+    ];
+
+    Some((psnr, errors))
+}
+
+fn ssim(metadata: &DictionaryRef) -> Option<Ssim> {
+    Some(Ssim {
+        y: parse(metadata, "lavfi.ssim.Y")?,
+        u: parse(metadata, "lavfi.ssim.U")?,
+        v: parse(metadata, "lavfi.ssim.V")?,
+        all: parse(metadata, "lavfi.ssim.All")?,
+    })
+}
+
+/// Creates an empty `libvmaf` log in the temporary directory, accessible to
+/// the current user only and named at random so that it cannot be created or
+/// replaced beforehand.
+#[cfg(feature = "build-lib-vmaf")]
+fn create_log() -> Result<PathBuf, Error> {
+    use std::collections::hash_map::RandomState;
+    use std::fs::OpenOptions;
+    use std::hash::{BuildHasher, Hasher};
+    use std::io::ErrorKind;
+
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    for _ in 0..16 {
+        // randomly keyed, so every state hashes to a different value
+        let name = RandomState::new().build_hasher().finish();
+        let path = std::env::temp_dir().join(format!("libvmaf-{name:016x}.csv"));
+
+        match options.open(&path) {
+            Ok(_) => return Ok(path),
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+            Err(e) => {
+                return Err(Error::Other {
+                    errno: e.raw_os_error().unwrap_or(libc::EIO),
+                })
+            }
+        }
+    }
+
+    Err(Error::Other {
+        errno: libc::EEXIST,
+    })
+}
+
+/// Reads the `vmaf` column of a CSV log written by `libvmaf`.
+#[cfg(feature = "build-lib-vmaf")]
+fn vmaf_scores(log: &str) -> Vec<f64> {
+    let mut lines = log.lines();
+    let Some(column) = lines
+        .next()
+        .and_then(|header| header.split(',').position(|name| name.trim() == "vmaf"))
+    else {
+        return Vec::new();
+    };
+
+    lines
+        .filter_map(|line| line.split(',').nth(column)?.trim().parse().ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(format: format::Pixel, width: u32, height: u32, value: u8) -> frame::Video {
+        let mut frame = frame::Video::new(format, width, height);
+        for plane in 0..frame.planes() {
+            frame.data_mut(plane).fill(value);
+        }
+
+        frame
+    }
+
+    #[test]
+    fn test_identical() {
+        let mut comparator = Comparator::new(&[Metric::Psnr, Metric::Ssim]);
+
+        for _ in 0..3 {
+            let reference = frame(format::Pixel::YUV420P, 64, 64, 128);
+            let distorted = frame(format::Pixel::YUV444P, 32, 32, 128);
+
+            comparator.send(&reference, &distorted).unwrap();
+        }
+
+        let summary = comparator.finish().unwrap();
+        assert_eq!(summary.frames.len(), 3);
+        assert_eq!(summary.frames[2].index, 2);
+        assert!(summary.frames.iter().all(|s| s.ssim.unwrap().all > 0.99));
+        assert!(summary.psnr.unwrap().average > 40.0);
+        assert!(summary.ssim.unwrap().y > 0.99);
+        assert_eq!(summary.vmaf, None);
+    }
+
+    #[test]
+    fn test_duplicate_metrics() {
+        let mut comparator = Comparator::new(&[Metric::Psnr, Metric::Psnr]);
+        let reference = frame(format::Pixel::YUV420P, 32, 32, 128);
+
+        comparator.send(&reference, &reference).unwrap();
+        assert!(comparator.finish().unwrap().psnr.is_some());
+    }
+
+    #[cfg(feature = "build-lib-vmaf")]
+    #[test]
+    fn test_vmaf_scores() {
+        let log = "Frame,integer_adm2,vmaf,\n0,0.98,95.5,\n1,0.97,94.0,\n";
+        assert_eq!(vmaf_scores(log), vec![95.5, 94.0]);
+        assert_eq!(vmaf_scores("Frame,psnr_y,\n0,40.0,\n"), Vec::<f64>::new());
+    }
+}
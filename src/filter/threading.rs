@@ -0,0 +1,61 @@
+use crate::ffi::*;
+use libc::c_int;
+
+/// Threading of the filters in a graph, see
+/// [`Graph::set_threading`](super::Graph::set_threading).
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub struct Config {
+    pub kind: Type,
+    /// Maximum number of threads, `0` picks one based on the CPU count.
+    pub count: usize,
+}
+
+impl Config {
+    pub fn kind(value: Type) -> Self {
+        Config {
+            kind: value,
+            ..Default::default()
+        }
+    }
+
+    pub fn count(value: usize) -> Self {
+        Config {
+            count: value,
+            ..Default::default()
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            kind: Type::Slice,
+            count: 0,
+        }
+    }
+}
+
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum Type {
+    None,
+    Slice,
+}
+
+impl From<c_int> for Type {
+    fn from(value: c_int) -> Type {
+        match value {
+            AVFILTER_THREAD_SLICE => Type::Slice,
+
+            _ => Type::None,
+        }
+    }
+}
+
+impl From<Type> for c_int {
+    fn from(value: Type) -> c_int {
+        match value {
+            Type::None => 0,
+            Type::Slice => AVFILTER_THREAD_SLICE,
+        }
+    }
+}
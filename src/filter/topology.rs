@@ -0,0 +1,214 @@
+//! Structured view of a filter graph, see [`Graph::topology`](super::Graph::topology).
+
+use std::fmt::Write;
+use std::mem;
+use std::slice;
+
+use super::context::Format;
+use crate::ffi::*;
+use crate::{format, media, utils, ChannelLayout, Rational};
+use libc::c_int;
+
+/// A filter instance of the graph.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Node {
+    pub name: String,
+    /// Name of the filter, e.g. `scale`.
+    pub filter: String,
+    pub inputs: usize,
+    pub outputs: usize,
+}
+
+/// A link between two filter instances, with the properties negotiated when
+/// the graph was validated.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Link {
+    pub src: String,
+    pub src_pad: usize,
+    pub src_pad_name: Option<String>,
+    pub dst: String,
+    pub dst_pad: usize,
+    pub dst_pad_name: Option<String>,
+
+    pub medium: media::Type,
+    /// `None` until formats are negotiated.
+    pub format: Option<Format>,
+    pub time_base: Rational,
+
+    pub width: u32,
+    pub height: u32,
+    pub aspect_ratio: Rational,
+
+    pub sample_rate: u32,
+    pub ch_layout: Option<ChannelLayout<'static>>,
+}
+
+impl Link {
+    /// Short description of the negotiated properties, e.g.
+    /// `yuv420p 1920x1080 1/25`.
+    pub fn summary(&self) -> String {
+        let format = match self.format {
+            Some(Format::Pixel(format)) => format.descriptor().map(|d| d.name()),
+            Some(Format::Sample(format)) => Some(format.name()),
+            None => None,
+        };
+
+        let mut summary = String::from(format.unwrap_or("?"));
+
+        match self.medium {
+            media::Type::Video => {
+                let _ = write!(summary, " {}x{}", self.width, self.height);
+            }
+            media::Type::Audio => {
+                let _ = write!(summary, " {}Hz", self.sample_rate);
+
+                if let Some(ch_layout) = &self.ch_layout {
+                    let _ = write!(summary, " {}", ch_layout.description());
+                }
+            }
+            _ => (),
+        }
+
+        let _ = write!(summary, " {}", self.time_base);
+
+        summary
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Topology {
+    pub nodes: Vec<Node>,
+    pub links: Vec<Link>,
+}
+
+impl Topology {
+    /// Reads the filter instances and links of `graph`.
+    pub unsafe fn from_raw(graph: *const AVFilterGraph) -> Self {
+        let mut topology = Topology::default();
+
+        if (*graph).filters.is_null() {
+            return topology;
+        }
+
+        for &context in slice::from_raw_parts((*graph).filters, (*graph).nb_filters as usize) {
+            topology.nodes.push(Node {
+                name: utils::str_from_c_ptr((*context).name).to_owned(),
+                filter: utils::str_from_c_ptr((*(*context).filter).name).to_owned(),
+                inputs: (*context).nb_inputs as usize,
+                outputs: (*context).nb_outputs as usize,
+            });
+
+            for i in 0..(*context).nb_outputs as usize {
+                let link = *(*context).outputs.add(i);
+
+                if !link.is_null() {
+                    topology.links.push(link_from_raw(link));
+                }
+            }
+        }
+
+        topology
+    }
+
+    /// Renders the graph in the Graphviz DOT language, links labelled with
+    /// their [`Link::summary`].
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph {\n");
+
+        for node in &self.nodes {
+            let _ = writeln!(
+                dot,
+                "    \"{}\" [label=\"{}\\n({})\"];",
+                escape(&node.name),
+                escape(&node.name),
+                escape(&node.filter),
+            );
+        }
+
+        for link in &self.links {
+            let _ = writeln!(
+                dot,
+                "    \"{}\" -> \"{}\" [label=\"{}\"];",
+                escape(&link.src),
+                escape(&link.dst),
+                escape(&link.summary()),
+            );
+        }
+
+        dot.push_str("}\n");
+
+        dot
+    }
+}
+
+unsafe fn link_from_raw(link: *const AVFilterLink) -> Link {
+    let medium = media::Type::from((*link).type_);
+    let format = match ((*link).format, medium) {
+        (-1, _) => None,
+        (n, media::Type::Video) => Some(Format::Pixel(format::Pixel::from(mem::transmute::<
+            c_int,
+            AVPixelFormat,
+        >(n)))),
+        (n, media::Type::Audio) => Some(Format::Sample(format::Sample::from(mem::transmute::<
+            c_int,
+            AVSampleFormat,
+        >(n)))),
+        _ => None,
+    };
+
+    let ch_layout = if (*link).ch_layout.nb_channels > 0 {
+        let mut layout = AVChannelLayout::empty();
+
+        match av_channel_layout_copy(&mut layout, &(*link).ch_layout) {
+            0 => Some(ChannelLayout::from(layout)),
+            _ => None,
+        }
+    } else {
+        None
+    };
+
+    Link {
+        src: utils::str_from_c_ptr((*(*link).src).name).to_owned(),
+        src_pad: pad_index((*(*link).src).output_pads, (*link).srcpad),
+        src_pad_name: pad_name((*link).srcpad),
+        dst: utils::str_from_c_ptr((*(*link).dst).name).to_owned(),
+        dst_pad: pad_index((*(*link).dst).input_pads, (*link).dstpad),
+        dst_pad_name: pad_name((*link).dstpad),
+
+        medium,
+        format,
+        time_base: Rational::from((*link).time_base),
+
+        width: (*link).w.max(0) as u32,
+        height: (*link).h.max(0) as u32,
+        aspect_ratio: Rational::from((*link).sample_aspect_ratio),
+
+        sample_rate: (*link).sample_rate.max(0) as u32,
+        ch_layout,
+    }
+}
+
+/// Returns the index of `pad` within the pad array starting at `pads`.
+unsafe fn pad_index(pads: *const AVFilterPad, pad: *const AVFilterPad) -> usize {
+    pad.offset_from(pads) as usize
+}
+
+unsafe fn pad_name(pad: *const AVFilterPad) -> Option<String> {
+    utils::optional_str_from_c_ptr(avfilter_pad_get_name(pad, 0)).map(String::from)
+}
+
+/// Escapes a string for use inside a quoted DOT identifier.
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("Parsed_scale_0"), "Parsed_scale_0");
+        assert_eq!(escape(r#"a"b\c"#), r#"a\"b\\c"#);
+    }
+}
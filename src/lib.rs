@@ -6,15 +6,18 @@ pub use crate::util::channel_layout::{
     self, Channel, ChannelCustom, ChannelLayout, ChannelLayoutIter, ChannelLayoutMask, ChannelOrder,
 };
 pub use crate::util::{
-    chroma, color, dictionary,
+    chroma, color, detection_bbox, dictionary,
     dictionary::{Dictionary, DictionaryMut, DictionaryRef},
+    display, dovi,
     error::{self, Error},
+    exif, film_grain,
     frame::{self, Frame},
-    log,
+    hdr, icc, log,
     mathematics::{self, rescale, Rescale, Rounding},
-    media, option, picture,
+    media, motion_vector, option, picture,
     rational::{self, Rational},
-    time,
+    region_of_interest::{self, RegionOfInterest},
+    sei, spherical, stereo3d, time, timecode, user_data, video_enc_params,
 };
 
 #[cfg(feature = "ffmpeg_8_1")]
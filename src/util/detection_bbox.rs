@@ -0,0 +1,210 @@
+//! Object detection results, as produced by the `dnn_detect` and
+//! `dnn_classify` filters.
+
+use std::ffi::CStr;
+use std::mem;
+
+use libc::c_char;
+
+use crate::ffi::*;
+use crate::frame::{side_data::Type, Frame};
+use crate::{utils, Error, Rational};
+
+/// Maximum number of classifications per bounding box.
+pub const MAX_CLASSIFICATIONS: usize = AV_NUM_DETECTION_BBOX_CLASSIFY as usize;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Classification {
+    pub label: String,
+    pub confidence: Rational,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DetectionBBox {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+
+    pub label: String,
+    pub confidence: Rational,
+
+    /// At most [`MAX_CLASSIFICATIONS`] entries.
+    pub classifications: Vec<Classification>,
+}
+
+impl DetectionBBox {
+    pub fn new(x: i32, y: i32, width: i32, height: i32, label: &str, confidence: Rational) -> Self {
+        DetectionBBox {
+            x,
+            y,
+            width,
+            height,
+            label: label.to_owned(),
+            confidence,
+            classifications: Vec::new(),
+        }
+    }
+}
+
+/// The bounding boxes attached to a frame by one detector.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Detections {
+    /// Name of the detector, e.g. the model file of `dnn_detect`.
+    pub source: String,
+    pub boxes: Vec<DetectionBBox>,
+}
+
+impl Detections {
+    pub fn new(source: &str) -> Self {
+        Detections {
+            source: source.to_owned(),
+            boxes: Vec::new(),
+        }
+    }
+
+    /// Parses the raw side data payload, returning `None` if it is truncated.
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        let header = unsafe { utils::read_struct::<AVDetectionBBoxHeader>(data)? };
+
+        if header.nb_bboxes > 0 && header.bbox_size < mem::size_of::<AVDetectionBBox>() {
+            return None;
+        }
+
+        let boxes = (0..header.nb_bboxes as usize)
+            .map(|i| {
+                let offset = header
+                    .bboxes_offset
+                    .checked_add(i.checked_mul(header.bbox_size)?)?;
+                let bbox = unsafe { utils::read_struct::<AVDetectionBBox>(data.get(offset..)?)? };
+
+                Some(DetectionBBox::from(&bbox))
+            })
+            .collect::<Option<_>>()?;
+
+        Some(Detections {
+            source: read_label(&header.source),
+            boxes,
+        })
+    }
+}
+
+impl<'a> From<&'a AVDetectionBBox> for DetectionBBox {
+    fn from(value: &'a AVDetectionBBox) -> Self {
+        let count = (value.classify_count as usize).min(MAX_CLASSIFICATIONS);
+
+        DetectionBBox {
+            x: value.x,
+            y: value.y,
+            width: value.w,
+            height: value.h,
+            label: read_label(&value.detect_label),
+            confidence: value.detect_confidence.into(),
+            classifications: value.classify_labels[..count]
+                .iter()
+                .zip(&value.classify_confidences)
+                .map(|(label, &confidence)| Classification {
+                    label: read_label(label),
+                    confidence: confidence.into(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Frame {
+    pub fn detection_bboxes(&self) -> Option<Detections> {
+        Detections::from_bytes(self.side_data(Type::DETECTION_BBOXES)?.data())
+    }
+
+    /// Attaches detection results, replacing any existing entry. Labels longer
+    /// than FFmpeg's limit are truncated.
+    pub fn set_detection_bboxes(&mut self, value: &Detections) -> Result<(), Error> {
+        if value
+            .boxes
+            .iter()
+            .any(|bbox| bbox.classifications.len() > MAX_CLASSIFICATIONS)
+        {
+            return Err(Error::Other {
+                errno: libc::EINVAL,
+            });
+        }
+
+        self.remove_side_data(Type::DETECTION_BBOXES);
+
+        unsafe {
+            let header =
+                av_detection_bbox_create_side_data(self.as_mut_ptr(), value.boxes.len() as u32);
+
+            if header.is_null() {
+                return Err(Error::Other {
+                    errno: libc::ENOMEM,
+                });
+            }
+
+            write_label(&mut (*header).source, &value.source);
+
+            for (i, bbox) in value.boxes.iter().enumerate() {
+                let raw = &mut *((header as *mut u8)
+                    .add((*header).bboxes_offset + i * (*header).bbox_size)
+                    as *mut AVDetectionBBox);
+
+                raw.x = bbox.x;
+                raw.y = bbox.y;
+                raw.w = bbox.width;
+                raw.h = bbox.height;
+                write_label(&mut raw.detect_label, &bbox.label);
+                raw.detect_confidence = bbox.confidence.into();
+                raw.classify_count = bbox.classifications.len() as u32;
+
+                for (j, classification) in bbox.classifications.iter().enumerate() {
+                    write_label(&mut raw.classify_labels[j], &classification.label);
+                    raw.classify_confidences[j] = classification.confidence.into();
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn read_label(label: &[c_char]) -> String {
+    let bytes = unsafe { &*(label as *const [c_char] as *const [u8]) };
+
+    match CStr::from_bytes_until_nul(bytes) {
+        Ok(label) => label.to_string_lossy().into_owned(),
+        Err(_) => String::from_utf8_lossy(bytes).into_owned(),
+    }
+}
+
+/// Copies `value` NUL-terminated into `label`, truncating it on a character boundary.
+fn write_label(label: &mut [c_char], value: &str) {
+    let mut length = value.len().min(label.len() - 1);
+    while !value.is_char_boundary(length) {
+        length -= 1;
+    }
+
+    for (dst, &src) in label.iter_mut().zip(&value.as_bytes()[..length]) {
+        *dst = src as c_char;
+    }
+    label[length] = 0;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labels() {
+        let mut label = [0 as c_char; 8];
+
+        write_label(&mut label, "face");
+        assert_eq!(read_label(&label), "face");
+
+        write_label(&mut label, "pedestrian");
+        assert_eq!(read_label(&label), "pedestr");
+
+        write_label(&mut label, "Gesiché");
+        assert_eq!(read_label(&label), "Gesich");
+    }
+}
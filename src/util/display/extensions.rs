@@ -0,0 +1,220 @@
+use std::mem;
+use std::ptr;
+
+use super::DisplayMatrix;
+use crate::ffi::*;
+use crate::frame::{side_data::Type, Frame};
+use crate::{utils, Error};
+
+#[cfg(feature = "codec")]
+use crate::codec::packet;
+#[cfg(all(feature = "codec", feature = "ffmpeg_6_1"))]
+use crate::codec::{Parameters, ParametersMut, ParametersRef};
+#[cfg(feature = "filter")]
+use crate::filter;
+#[cfg(feature = "format")]
+use crate::format::stream::{Stream, StreamMut};
+#[cfg(feature = "filter")]
+use crate::frame;
+#[cfg(all(feature = "codec", feature = "ffmpeg_6_1"))]
+use crate::macros::impl_for_many;
+
+impl Frame {
+    pub fn display_matrix(&self) -> Option<DisplayMatrix> {
+        let side_data = self.side_data(Type::DisplayMatrix)?;
+
+        unsafe { utils::read_struct::<[i32; 9]>(side_data.data()) }.map(DisplayMatrix::from)
+    }
+
+    /// Attaches a display matrix, replacing any existing entry.
+    pub fn set_display_matrix(&mut self, value: DisplayMatrix) -> Result<(), Error> {
+        self.remove_side_data(Type::DisplayMatrix);
+
+        let mut side_data = self
+            .new_side_data(Type::DisplayMatrix, mem::size_of::<[i32; 9]>())
+            .ok_or(Error::Other {
+                errno: libc::ENOMEM,
+            })?;
+
+        unsafe {
+            ptr::write_unaligned(
+                (*side_data.as_mut_ptr()).data as *mut [i32; 9],
+                value.into(),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "filter")]
+impl frame::Video {
+    /// Returns a copy of this frame transformed so that it is displayed upright
+    /// according to `matrix`. The copy carries no display matrix side data.
+    pub fn apply_display_matrix(&self, matrix: &DisplayMatrix) -> Result<frame::Video, Error> {
+        let Some(spec) = matrix.orientation().and_then(|o| o.filter_spec()) else {
+            let mut output = self.clone();
+            output.remove_side_data(Type::DisplayMatrix);
+
+            return Ok(output);
+        };
+
+        let mut graph = filter::Graph::new();
+        let aspect = self.aspect_ratio();
+        let args = format!(
+            "video_size={}x{}:pix_fmt={}:time_base=1/1:pixel_aspect={}/{}",
+            self.width(),
+            self.height(),
+            AVPixelFormat::from(self.format()).0,
+            aspect.numerator(),
+            aspect.denominator(),
+        );
+
+        let buffer = filter::find("buffer").ok_or(Error::FilterNotFound)?;
+        let buffersink = filter::find("buffersink").ok_or(Error::FilterNotFound)?;
+
+        graph.add(&buffer, "in", &args)?;
+        graph.add(&buffersink, "out", "")?;
+        graph.output("in", 0)?.input("out", 0)?.parse(&spec)?;
+        graph.validate()?;
+
+        let mut input = self.clone();
+        input.remove_side_data(Type::DisplayMatrix);
+
+        let mut source = graph.buffer_source("in")?;
+        source.add(&input)?;
+        source.flush()?;
+
+        let mut output = frame::Video::empty();
+        graph.buffer_sink("out")?.frame(&mut output)?;
+
+        Ok(output)
+    }
+
+    /// Returns a copy of this frame rotated and flipped according to its own
+    /// display matrix side data, which is removed from the copy.
+    pub fn upright(&self) -> Result<frame::Video, Error> {
+        self.apply_display_matrix(&self.display_matrix().unwrap_or_default())
+    }
+}
+
+#[cfg(feature = "codec")]
+impl<'a> packet::SideData<'a> {
+    /// Returns the display matrix if this is a `DisplayMatrix` entry.
+    pub fn display_matrix(&self) -> Option<DisplayMatrix> {
+        unsafe { self.payload::<[i32; 9]>(packet::side_data::Type::DisplayMatrix) }
+            .map(DisplayMatrix::from)
+    }
+}
+
+#[cfg(feature = "codec")]
+impl packet::Packet {
+    /// Attaches a display matrix, replacing any existing entry.
+    pub fn set_display_matrix(&mut self, value: DisplayMatrix) -> Result<(), Error> {
+        let matrix: [i32; 9] = value.into();
+        let data: Vec<u8> = matrix.iter().flat_map(|v| v.to_ne_bytes()).collect();
+
+        self.add_side_data(packet::side_data::Type::DisplayMatrix, &data)
+    }
+}
+
+#[cfg(all(feature = "codec", feature = "ffmpeg_6_1"))]
+impl_for_many! {
+    impl for Parameters, ParametersRef<'p>, ParametersMut<'p> {
+        pub fn display_matrix(&self) -> Option<DisplayMatrix> {
+            self.coded_side_data(packet::side_data::Type::DisplayMatrix)?
+                .display_matrix()
+        }
+    }
+}
+
+#[cfg(all(feature = "codec", feature = "ffmpeg_6_1"))]
+impl_for_many! {
+    impl for Parameters, ParametersMut<'p> {
+        /// Attaches a display matrix, replacing any existing entry.
+        pub fn set_display_matrix(&mut self, value: DisplayMatrix) -> Result<(), Error> {
+            unsafe {
+                let ptr = self.new_coded_side_data(
+                    packet::side_data::Type::DisplayMatrix,
+                    mem::size_of::<[i32; 9]>(),
+                )?;
+                ptr::write_unaligned(ptr as *mut [i32; 9], value.into());
+            }
+
+            Ok(())
+        }
+    }
+}
+
+#[cfg(feature = "format")]
+impl<'a> Stream<'a> {
+    #[cfg(feature = "ffmpeg_6_1")]
+    pub fn display_matrix(&self) -> Option<DisplayMatrix> {
+        self.parameters().display_matrix()
+    }
+
+    #[cfg(not(feature = "ffmpeg_6_1"))]
+    pub fn display_matrix(&self) -> Option<DisplayMatrix> {
+        unsafe {
+            let mut size = 0;
+            let ptr = av_stream_get_side_data(
+                self.as_ptr(),
+                AVPacketSideDataType::DISPLAYMATRIX,
+                &mut size,
+            );
+
+            if ptr.is_null() {
+                return None;
+            }
+
+            utils::read_struct::<[i32; 9]>(std::slice::from_raw_parts(ptr, size as usize))
+                .map(DisplayMatrix::from)
+        }
+    }
+}
+
+#[cfg(feature = "format")]
+impl<'a> StreamMut<'a> {
+    /// Attaches a display matrix, replacing any existing entry.
+    #[cfg(feature = "ffmpeg_6_1")]
+    pub fn set_display_matrix(&mut self, value: DisplayMatrix) -> Result<(), Error> {
+        self.parameters_mut().set_display_matrix(value)
+    }
+
+    /// Attaches a display matrix, replacing any existing entry.
+    #[cfg(not(feature = "ffmpeg_6_1"))]
+    pub fn set_display_matrix(&mut self, value: DisplayMatrix) -> Result<(), Error> {
+        unsafe {
+            let ptr = av_stream_new_side_data(
+                self.as_mut_ptr(),
+                AVPacketSideDataType::DISPLAYMATRIX,
+                mem::size_of::<[i32; 9]>(),
+            );
+
+            if ptr.is_null() {
+                return Err(Error::Other {
+                    errno: libc::ENOMEM,
+                });
+            }
+
+            ptr::write_unaligned(ptr as *mut [i32; 9], value.into());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(all(feature = "codec", feature = "ffmpeg_7_0"))]
+impl crate::codec::encoder::video::Video {
+    /// Sets the display matrix the encoder exports as stream side data, replacing
+    /// any existing entry. Must be called before opening the encoder.
+    pub fn set_display_matrix(&mut self, value: DisplayMatrix) -> Result<(), Error> {
+        unsafe {
+            let ptr =
+                self.new_decoded_side_data(Type::DisplayMatrix, mem::size_of::<[i32; 9]>())?;
+            ptr::write_unaligned(ptr as *mut [i32; 9], value.into());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,215 @@
+use crate::ffi::*;
+
+/// A 3x3 display transformation matrix, applied to (x, y, 1) row vectors.
+///
+/// The first two columns are 16.16 fixed point values, the third column is 2.30.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct DisplayMatrix([i32; 9]);
+
+impl DisplayMatrix {
+    pub fn identity() -> Self {
+        DisplayMatrix([1 << 16, 0, 0, 0, 1 << 16, 0, 0, 0, 1 << 30])
+    }
+
+    /// Creates a matrix rotating counterclockwise by `angle` degrees.
+    pub fn from_rotation(angle: f64) -> Self {
+        let mut matrix = [0; 9];
+
+        unsafe {
+            av_display_rotation_set(matrix.as_mut_ptr(), angle);
+        }
+
+        DisplayMatrix(matrix)
+    }
+
+    /// Returns a copy of this matrix with horizontal and/or vertical flipping appended.
+    pub fn flipped(mut self, horizontal: bool, vertical: bool) -> Self {
+        unsafe {
+            av_display_matrix_flip(self.0.as_mut_ptr(), horizontal as _, vertical as _);
+        }
+
+        self
+    }
+
+    pub fn values(&self) -> &[i32; 9] {
+        &self.0
+    }
+
+    /// Counterclockwise rotation in degrees within [-180, 180], or `None` if the
+    /// matrix is singular.
+    pub fn rotation(&self) -> Option<f64> {
+        let angle = unsafe { av_display_rotation_get(self.0.as_ptr()) };
+
+        (!angle.is_nan()).then_some(angle)
+    }
+
+    /// Whether the matrix mirrors the image, i.e. has a negative determinant.
+    pub fn is_mirrored(&self) -> bool {
+        (self.0[0] as i64) * (self.0[4] as i64) - (self.0[1] as i64) * (self.0[3] as i64) < 0
+    }
+
+    /// Decomposes the matrix into the steps needed to display the frame upright.
+    pub fn orientation(&self) -> Option<Orientation> {
+        let hflip = self.is_mirrored();
+        let unflipped = if hflip {
+            self.flipped(true, false)
+        } else {
+            *self
+        };
+
+        // same normalization as the ffmpeg CLI autorotation
+        let mut rotation = -unflipped.rotation()?.round();
+        rotation -= 360.0 * (rotation / 360.0 + 0.9 / 360.0).floor();
+
+        if hflip && (rotation - 180.0).abs() < 1.0 {
+            return Some(Orientation {
+                rotation: 0.0,
+                hflip: false,
+                vflip: true,
+            });
+        }
+
+        Some(Orientation {
+            rotation,
+            hflip,
+            vflip: false,
+        })
+    }
+}
+
+impl Default for DisplayMatrix {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl From<[i32; 9]> for DisplayMatrix {
+    fn from(value: [i32; 9]) -> Self {
+        DisplayMatrix(value)
+    }
+}
+
+impl From<DisplayMatrix> for [i32; 9] {
+    fn from(value: DisplayMatrix) -> Self {
+        value.0
+    }
+}
+
+/// Transformation to apply to a decoded frame so that it is displayed upright:
+/// first rotate clockwise by `rotation`, then flip.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Orientation {
+    /// Clockwise rotation in degrees, within [0, 360).
+    pub rotation: f64,
+    pub hflip: bool,
+    pub vflip: bool,
+}
+
+impl Orientation {
+    pub fn is_identity(&self) -> bool {
+        self.rotation.abs() < 1.0 && !self.hflip && !self.vflip
+    }
+
+    /// Returns a filter chain (for example `transpose=clock`) performing this
+    /// transformation, or `None` if the frame is already upright.
+    pub fn filter_spec(&self) -> Option<String> {
+        let near = |angle: f64| (self.rotation - angle).abs() < 1.0;
+
+        let rotate = if near(90.0) {
+            Some(
+                if self.hflip {
+                    "transpose=cclock_flip"
+                } else {
+                    "transpose=clock"
+                }
+                .to_owned(),
+            )
+        } else if near(180.0) {
+            Some(if self.hflip { "vflip" } else { "hflip,vflip" }.to_owned())
+        } else if near(270.0) {
+            Some(
+                if self.hflip {
+                    "transpose=clock_flip"
+                } else {
+                    "transpose=cclock"
+                }
+                .to_owned(),
+            )
+        } else if self.rotation.abs() >= 1.0 {
+            let spec = format!("rotate={}*PI/180", self.rotation);
+            Some(if self.hflip { spec + ",hflip" } else { spec })
+        } else {
+            None
+        };
+
+        let mut filters: Vec<String> = Vec::new();
+
+        match rotate {
+            Some(spec) => filters.push(spec),
+            None if self.hflip => filters.push("hflip".to_owned()),
+            None => (),
+        }
+
+        if self.vflip {
+            filters.push("vflip".to_owned());
+        }
+
+        (!filters.is_empty()).then(|| filters.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn orientation(matrix: DisplayMatrix) -> (f64, bool, bool) {
+        let o = matrix.orientation().unwrap();
+        (o.rotation, o.hflip, o.vflip)
+    }
+
+    #[test]
+    fn rotation() {
+        assert_eq!(DisplayMatrix::identity().rotation(), Some(0.0));
+        assert!((DisplayMatrix::from_rotation(90.0).rotation().unwrap() - 90.0).abs() < 1e-6);
+        assert_eq!(DisplayMatrix::from([0; 9]).rotation(), None);
+    }
+
+    #[test]
+    fn orientations() {
+        assert_eq!(orientation(DisplayMatrix::identity()), (0.0, false, false));
+        assert_eq!(
+            orientation(DisplayMatrix::from_rotation(-90.0)),
+            (90.0, false, false)
+        );
+        assert_eq!(
+            orientation(DisplayMatrix::from_rotation(90.0)),
+            (270.0, false, false)
+        );
+
+        let hflip = DisplayMatrix::identity().flipped(true, false);
+        assert!(hflip.is_mirrored());
+        assert_eq!(orientation(hflip), (0.0, true, false));
+
+        let vflip = DisplayMatrix::identity().flipped(false, true);
+        assert_eq!(orientation(vflip), (0.0, false, true));
+    }
+
+    #[test]
+    fn filter_specs() {
+        let spec = |m: DisplayMatrix| m.orientation().unwrap().filter_spec();
+
+        assert_eq!(spec(DisplayMatrix::identity()), None);
+        assert_eq!(
+            spec(DisplayMatrix::from_rotation(-90.0)).as_deref(),
+            Some("transpose=clock")
+        );
+        assert_eq!(
+            spec(DisplayMatrix::from_rotation(-90.0).flipped(true, false)).as_deref(),
+            Some("transpose=cclock_flip")
+        );
+        assert_eq!(
+            spec(DisplayMatrix::from_rotation(180.0)).as_deref(),
+            Some("hflip,vflip")
+        );
+    }
+}
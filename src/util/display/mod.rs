@@ -0,0 +1,6 @@
+//! Display transformation matrices, as carried by `DisplayMatrix` side data.
+
+pub mod matrix;
+pub use self::matrix::{DisplayMatrix, Orientation};
+
+mod extensions;
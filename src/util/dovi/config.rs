@@ -0,0 +1,52 @@
+use std::mem;
+
+use crate::ffi::*;
+
+/// Dolby Vision decoder configuration record (`dvcC`/`dvvC` box), as found in
+/// `DOVI_CONF` stream side data.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct DecoderConfigurationRecord {
+    pub version_major: u8,
+    pub version_minor: u8,
+    pub profile: u8,
+    pub level: u8,
+    pub rpu_present: bool,
+    pub el_present: bool,
+    pub bl_present: bool,
+    /// Compatibility of the base layer with non Dolby Vision decoders,
+    /// e.g. 1 for HDR10 or 4 for HLG.
+    pub bl_signal_compatibility_id: u8,
+}
+
+impl From<AVDOVIDecoderConfigurationRecord> for DecoderConfigurationRecord {
+    fn from(value: AVDOVIDecoderConfigurationRecord) -> Self {
+        DecoderConfigurationRecord {
+            version_major: value.dv_version_major,
+            version_minor: value.dv_version_minor,
+            profile: value.dv_profile,
+            level: value.dv_level,
+            rpu_present: value.rpu_present_flag != 0,
+            el_present: value.el_present_flag != 0,
+            bl_present: value.bl_present_flag != 0,
+            bl_signal_compatibility_id: value.dv_bl_signal_compatibility_id,
+        }
+    }
+}
+
+impl From<DecoderConfigurationRecord> for AVDOVIDecoderConfigurationRecord {
+    fn from(value: DecoderConfigurationRecord) -> Self {
+        // newer FFmpeg versions append fields, leave them zeroed
+        let mut raw: AVDOVIDecoderConfigurationRecord = unsafe { mem::zeroed() };
+
+        raw.dv_version_major = value.version_major;
+        raw.dv_version_minor = value.version_minor;
+        raw.dv_profile = value.profile;
+        raw.dv_level = value.level;
+        raw.rpu_present_flag = value.rpu_present as u8;
+        raw.el_present_flag = value.el_present as u8;
+        raw.bl_present_flag = value.bl_present as u8;
+        raw.dv_bl_signal_compatibility_id = value.bl_signal_compatibility_id;
+
+        raw
+    }
+}
@@ -0,0 +1,255 @@
+use std::ptr;
+use std::slice;
+
+use super::{DecoderConfigurationRecord, Metadata};
+use crate::ffi::*;
+use crate::frame::{side_data::Type, Frame};
+use crate::Error;
+
+#[cfg(feature = "codec")]
+use crate::codec::packet;
+#[cfg(all(feature = "codec", feature = "ffmpeg_6_1"))]
+use crate::codec::{Parameters, ParametersMut, ParametersRef};
+#[cfg(feature = "format")]
+use crate::format::stream::{Stream, StreamMut};
+#[cfg(all(feature = "codec", feature = "ffmpeg_6_1"))]
+use crate::macros::impl_for_many;
+
+impl Frame {
+    pub fn dovi_metadata(&self) -> Option<Metadata> {
+        Metadata::from_bytes(self.side_data(Type::DOVI_METADATA)?.data())
+    }
+
+    /// Attaches Dolby Vision RPU metadata, replacing any existing entry.
+    pub fn set_dovi_metadata(&mut self, value: &Metadata) -> Result<(), Error> {
+        #[cfg(feature = "ffmpeg_7_0")]
+        if value.ext_blocks.len() > AV_DOVI_MAX_EXT_BLOCKS as usize {
+            return Err(Error::Other {
+                errno: libc::EINVAL,
+            });
+        }
+
+        self.remove_side_data(Type::DOVI_METADATA);
+
+        unsafe {
+            // let FFmpeg lay out the sub-structures, with room for the extension blocks
+            let mut size = 0;
+            let template = av_dovi_metadata_alloc(&mut size);
+
+            if template.is_null() {
+                return Err(Error::Other {
+                    errno: libc::ENOMEM,
+                });
+            }
+
+            let Some(mut side_data) = self.new_side_data(Type::DOVI_METADATA, size) else {
+                av_free(template as *mut _);
+
+                return Err(Error::Other {
+                    errno: libc::ENOMEM,
+                });
+            };
+
+            let data = (*side_data.as_mut_ptr()).data;
+            ptr::copy_nonoverlapping(template as *const u8, data, size);
+
+            ptr::write_unaligned(
+                data.add((*template).header_offset) as *mut AVDOVIRpuDataHeader,
+                value.header.0,
+            );
+            ptr::write_unaligned(
+                data.add((*template).mapping_offset) as *mut AVDOVIDataMapping,
+                value.mapping.0,
+            );
+            ptr::write_unaligned(
+                data.add((*template).color_offset) as *mut AVDOVIColorMetadata,
+                value.color.0,
+            );
+
+            #[cfg(feature = "ffmpeg_7_0")]
+            {
+                for (i, block) in value.ext_blocks.iter().enumerate() {
+                    ptr::write_unaligned(
+                        data.add((*template).ext_block_offset + i * (*template).ext_block_size)
+                            as *mut AVDOVIDmData,
+                        block.0,
+                    );
+                }
+
+                (*(data as *mut AVDOVIMetadata)).num_ext_blocks = value.ext_blocks.len() as _;
+            }
+
+            av_free(template as *mut _);
+        }
+
+        Ok(())
+    }
+
+    /// The raw Dolby Vision RPU NAL unit, without the emulation prevention bytes.
+    pub fn dovi_rpu_buffer(&self) -> Option<&[u8]> {
+        let side_data = self.side_data(Type::DOVI_RPU_BUFFER)?;
+
+        // borrow the buffer for the lifetime of the frame rather than the side data handle
+        unsafe {
+            let ptr = side_data.as_ptr();
+            Some(slice::from_raw_parts((*ptr).data, (*ptr).size as usize))
+        }
+    }
+
+    /// Attaches a raw Dolby Vision RPU NAL unit, replacing any existing entry.
+    pub fn set_dovi_rpu_buffer(&mut self, value: &[u8]) -> Result<(), Error> {
+        self.remove_side_data(Type::DOVI_RPU_BUFFER);
+
+        let mut side_data = self
+            .new_side_data(Type::DOVI_RPU_BUFFER, value.len())
+            .ok_or(Error::Other {
+                errno: libc::ENOMEM,
+            })?;
+
+        unsafe {
+            ptr::copy_nonoverlapping(value.as_ptr(), (*side_data.as_mut_ptr()).data, value.len());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "codec")]
+impl<'a> packet::SideData<'a> {
+    /// Returns the configuration record if this is a `DOVI_CONF` entry.
+    pub fn dovi_configuration(&self) -> Option<DecoderConfigurationRecord> {
+        unsafe {
+            self.payload::<AVDOVIDecoderConfigurationRecord>(packet::side_data::Type::DOVI_CONF)
+        }
+        .map(DecoderConfigurationRecord::from)
+    }
+}
+
+#[cfg(all(feature = "codec", feature = "ffmpeg_6_1"))]
+impl_for_many! {
+    impl for Parameters, ParametersRef<'p>, ParametersMut<'p> {
+        pub fn dovi_configuration(&self) -> Option<DecoderConfigurationRecord> {
+            self.coded_side_data(packet::side_data::Type::DOVI_CONF)?
+                .dovi_configuration()
+        }
+    }
+}
+
+#[cfg(all(feature = "codec", feature = "ffmpeg_6_1"))]
+impl_for_many! {
+    impl for Parameters, ParametersMut<'p> {
+        /// Attaches a Dolby Vision configuration record, replacing any existing entry.
+        pub fn set_dovi_configuration(&mut self, value: DecoderConfigurationRecord) -> Result<(), Error> {
+            unsafe {
+                let ptr = self.new_coded_side_data(
+                    packet::side_data::Type::DOVI_CONF,
+                    std::mem::size_of::<AVDOVIDecoderConfigurationRecord>(),
+                )?;
+                ptr::write_unaligned(ptr as *mut AVDOVIDecoderConfigurationRecord, value.into());
+            }
+
+            Ok(())
+        }
+    }
+}
+
+#[cfg(feature = "format")]
+impl<'a> Stream<'a> {
+    #[cfg(feature = "ffmpeg_6_1")]
+    pub fn dovi_configuration(&self) -> Option<DecoderConfigurationRecord> {
+        self.parameters().dovi_configuration()
+    }
+
+    #[cfg(not(feature = "ffmpeg_6_1"))]
+    pub fn dovi_configuration(&self) -> Option<DecoderConfigurationRecord> {
+        unsafe {
+            let mut size = 0;
+            let ptr =
+                av_stream_get_side_data(self.as_ptr(), AVPacketSideDataType::DOVI_CONF, &mut size);
+
+            if ptr.is_null() {
+                return None;
+            }
+
+            crate::utils::read_struct::<AVDOVIDecoderConfigurationRecord>(slice::from_raw_parts(
+                ptr,
+                size as usize,
+            ))
+            .map(DecoderConfigurationRecord::from)
+        }
+    }
+}
+
+#[cfg(feature = "format")]
+impl<'a> StreamMut<'a> {
+    /// Attaches a Dolby Vision configuration record, replacing any existing entry.
+    #[cfg(feature = "ffmpeg_6_1")]
+    pub fn set_dovi_configuration(
+        &mut self,
+        value: DecoderConfigurationRecord,
+    ) -> Result<(), Error> {
+        self.parameters_mut().set_dovi_configuration(value)
+    }
+
+    /// Attaches a Dolby Vision configuration record, replacing any existing entry.
+    #[cfg(not(feature = "ffmpeg_6_1"))]
+    pub fn set_dovi_configuration(
+        &mut self,
+        value: DecoderConfigurationRecord,
+    ) -> Result<(), Error> {
+        unsafe {
+            let ptr = av_stream_new_side_data(
+                self.as_mut_ptr(),
+                AVPacketSideDataType::DOVI_CONF,
+                std::mem::size_of::<AVDOVIDecoderConfigurationRecord>(),
+            );
+
+            if ptr.is_null() {
+                return Err(Error::Other {
+                    errno: libc::ENOMEM,
+                });
+            }
+
+            ptr::write_unaligned(ptr as *mut AVDOVIDecoderConfigurationRecord, value.into());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "ffmpeg_7_0"))]
+mod tests {
+    use super::*;
+    use crate::{format, frame};
+
+    #[test]
+    fn ext_blocks() {
+        let mut metadata = unsafe {
+            let mut size = 0;
+            let raw = av_dovi_metadata_alloc(&mut size);
+            let metadata = Metadata::from_bytes(slice::from_raw_parts(raw as *const u8, size));
+            av_free(raw as *mut _);
+
+            metadata.unwrap()
+        };
+        assert!(metadata.ext_blocks.is_empty());
+
+        for level in [1, 2] {
+            let mut block: AVDOVIDmData = unsafe { std::mem::zeroed() };
+            block.level = level;
+            metadata.ext_blocks.push(block.into());
+        }
+
+        let mut frame = frame::Video::new(format::Pixel::YUV420P, 16, 16);
+        frame.set_dovi_metadata(&metadata).unwrap();
+
+        let levels = frame
+            .dovi_metadata()
+            .unwrap()
+            .ext_blocks
+            .iter()
+            .map(|block| block.level())
+            .collect::<Vec<_>>();
+        assert_eq!(levels, vec![1, 2]);
+    }
+}
@@ -0,0 +1,297 @@
+use crate::ffi::*;
+use crate::{utils, Rational};
+
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum NlqMethod {
+    None,
+    LinearDeadZone,
+}
+
+impl From<AVDOVINLQMethod> for NlqMethod {
+    fn from(value: AVDOVINLQMethod) -> Self {
+        use AVDOVINLQMethod as AV;
+
+        match value {
+            AV::NONE => NlqMethod::None,
+            AV::LINEAR_DZ => NlqMethod::LinearDeadZone,
+
+            _ => unimplemented!(),
+        }
+    }
+}
+
+impl From<NlqMethod> for AVDOVINLQMethod {
+    fn from(value: NlqMethod) -> Self {
+        use AVDOVINLQMethod as AV;
+
+        match value {
+            NlqMethod::None => AV::NONE,
+            NlqMethod::LinearDeadZone => AV::LINEAR_DZ,
+        }
+    }
+}
+
+/// Dolby Vision RPU metadata, as found in `DOVI_METADATA` frame side data.
+#[derive(Clone)]
+pub struct Metadata {
+    pub header: RpuDataHeader,
+    pub mapping: DataMapping,
+    pub color: ColorMetadata,
+    /// Display management extension blocks (L1, L2, ...), carrying the per-frame
+    /// dynamic metadata.
+    #[cfg(feature = "ffmpeg_7_0")]
+    pub ext_blocks: Vec<ExtBlock>,
+}
+
+impl Metadata {
+    /// Parses the raw side data payload, returning `None` if it is truncated.
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        unsafe {
+            let raw = utils::read_struct::<AVDOVIMetadata>(data)?;
+
+            Some(Metadata {
+                header: RpuDataHeader(utils::read_struct(data.get(raw.header_offset..)?)?),
+                mapping: DataMapping(utils::read_struct(data.get(raw.mapping_offset..)?)?),
+                color: ColorMetadata(utils::read_struct(data.get(raw.color_offset..)?)?),
+                #[cfg(feature = "ffmpeg_7_0")]
+                ext_blocks: (0..raw.num_ext_blocks.max(0) as usize)
+                    .map(|i| {
+                        let offset = i
+                            .checked_mul(raw.ext_block_size)?
+                            .checked_add(raw.ext_block_offset)?;
+
+                        utils::read_struct(data.get(offset..)?).map(ExtBlock)
+                    })
+                    .collect::<Option<_>>()?,
+            })
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct RpuDataHeader(pub(super) AVDOVIRpuDataHeader);
+
+impl RpuDataHeader {
+    pub fn rpu_type(&self) -> u8 {
+        self.0.rpu_type
+    }
+
+    pub fn rpu_format(&self) -> u16 {
+        self.0.rpu_format
+    }
+
+    pub fn vdr_rpu_profile(&self) -> u8 {
+        self.0.vdr_rpu_profile
+    }
+
+    pub fn vdr_rpu_level(&self) -> u8 {
+        self.0.vdr_rpu_level
+    }
+
+    pub fn coef_data_type(&self) -> u8 {
+        self.0.coef_data_type
+    }
+
+    pub fn coef_log2_denom(&self) -> u8 {
+        self.0.coef_log2_denom
+    }
+
+    pub fn bl_video_full_range(&self) -> bool {
+        self.0.bl_video_full_range_flag != 0
+    }
+
+    pub fn bl_bit_depth(&self) -> u8 {
+        self.0.bl_bit_depth
+    }
+
+    pub fn el_bit_depth(&self) -> u8 {
+        self.0.el_bit_depth
+    }
+
+    pub fn vdr_bit_depth(&self) -> u8 {
+        self.0.vdr_bit_depth
+    }
+
+    pub fn disable_residual(&self) -> bool {
+        self.0.disable_residual_flag != 0
+    }
+
+    #[inline(always)]
+    pub fn as_ptr(&self) -> *const AVDOVIRpuDataHeader {
+        &self.0
+    }
+
+    #[inline(always)]
+    pub fn as_mut_ptr(&mut self) -> *mut AVDOVIRpuDataHeader {
+        &mut self.0
+    }
+}
+
+impl From<AVDOVIRpuDataHeader> for RpuDataHeader {
+    fn from(value: AVDOVIRpuDataHeader) -> Self {
+        RpuDataHeader(value)
+    }
+}
+
+/// Reshaping and non-linear quantization of the base and enhancement layers.
+#[derive(Copy, Clone)]
+pub struct DataMapping(pub(super) AVDOVIDataMapping);
+
+impl DataMapping {
+    pub fn vdr_rpu_id(&self) -> u8 {
+        self.0.vdr_rpu_id
+    }
+
+    pub fn mapping_color_space(&self) -> u8 {
+        self.0.mapping_color_space
+    }
+
+    pub fn mapping_chroma_format_idc(&self) -> u8 {
+        self.0.mapping_chroma_format_idc
+    }
+
+    /// Pivot points of the reshaping curve of each component.
+    pub fn pivots(&self) -> [Vec<u16>; 3] {
+        self.0.curves.map(|curve| {
+            let count = (curve.num_pivots as usize).min(curve.pivots.len());
+            curve.pivots[..count].to_vec()
+        })
+    }
+
+    pub fn nlq_method(&self) -> NlqMethod {
+        self.0.nlq_method_idc.into()
+    }
+
+    pub fn num_x_partitions(&self) -> u32 {
+        self.0.num_x_partitions
+    }
+
+    pub fn num_y_partitions(&self) -> u32 {
+        self.0.num_y_partitions
+    }
+
+    #[inline(always)]
+    pub fn as_ptr(&self) -> *const AVDOVIDataMapping {
+        &self.0
+    }
+
+    #[inline(always)]
+    pub fn as_mut_ptr(&mut self) -> *mut AVDOVIDataMapping {
+        &mut self.0
+    }
+}
+
+impl From<AVDOVIDataMapping> for DataMapping {
+    fn from(value: AVDOVIDataMapping) -> Self {
+        DataMapping(value)
+    }
+}
+
+/// Display management metadata describing the source signal.
+#[derive(Copy, Clone)]
+pub struct ColorMetadata(pub(super) AVDOVIColorMetadata);
+
+impl ColorMetadata {
+    pub fn dm_metadata_id(&self) -> u8 {
+        self.0.dm_metadata_id
+    }
+
+    pub fn scene_refresh(&self) -> bool {
+        self.0.scene_refresh_flag != 0
+    }
+
+    /// Row-major 3x3 YCbCr to RGB matrix.
+    pub fn ycc_to_rgb_matrix(&self) -> [Rational; 9] {
+        self.0.ycc_to_rgb_matrix.map(Rational::from)
+    }
+
+    pub fn ycc_to_rgb_offset(&self) -> [Rational; 3] {
+        self.0.ycc_to_rgb_offset.map(Rational::from)
+    }
+
+    /// Row-major 3x3 RGB to LMS matrix.
+    pub fn rgb_to_lms_matrix(&self) -> [Rational; 9] {
+        self.0.rgb_to_lms_matrix.map(Rational::from)
+    }
+
+    pub fn signal_eotf(&self) -> u16 {
+        self.0.signal_eotf
+    }
+
+    pub fn signal_bit_depth(&self) -> u8 {
+        self.0.signal_bit_depth
+    }
+
+    pub fn signal_color_space(&self) -> u8 {
+        self.0.signal_color_space
+    }
+
+    pub fn signal_chroma_format(&self) -> u8 {
+        self.0.signal_chroma_format
+    }
+
+    pub fn signal_full_range(&self) -> bool {
+        self.0.signal_full_range_flag != 0
+    }
+
+    /// Minimum luminance of the source, as a 12-bit PQ code value.
+    pub fn source_min_pq(&self) -> u16 {
+        self.0.source_min_pq
+    }
+
+    /// Maximum luminance of the source, as a 12-bit PQ code value.
+    pub fn source_max_pq(&self) -> u16 {
+        self.0.source_max_pq
+    }
+
+    /// Diagonal of the source display, in inches.
+    pub fn source_diagonal(&self) -> u16 {
+        self.0.source_diagonal
+    }
+
+    #[inline(always)]
+    pub fn as_ptr(&self) -> *const AVDOVIColorMetadata {
+        &self.0
+    }
+
+    #[inline(always)]
+    pub fn as_mut_ptr(&mut self) -> *mut AVDOVIColorMetadata {
+        &mut self.0
+    }
+}
+
+impl From<AVDOVIColorMetadata> for ColorMetadata {
+    fn from(value: AVDOVIColorMetadata) -> Self {
+        ColorMetadata(value)
+    }
+}
+
+/// A display management extension block, kept opaque.
+#[cfg(feature = "ffmpeg_7_0")]
+#[derive(Copy, Clone)]
+pub struct ExtBlock(pub(super) AVDOVIDmData);
+
+#[cfg(feature = "ffmpeg_7_0")]
+impl ExtBlock {
+    /// Level of the block, e.g. 1 for L1 (per-frame brightness).
+    pub fn level(&self) -> u8 {
+        self.0.level
+    }
+
+    #[inline(always)]
+    pub fn as_ptr(&self) -> *const AVDOVIDmData {
+        &self.0
+    }
+
+    #[inline(always)]
+    pub fn as_mut_ptr(&mut self) -> *mut AVDOVIDmData {
+        &mut self.0
+    }
+}
+
+#[cfg(feature = "ffmpeg_7_0")]
+impl From<AVDOVIDmData> for ExtBlock {
+    fn from(value: AVDOVIDmData) -> Self {
+        ExtBlock(value)
+    }
+}
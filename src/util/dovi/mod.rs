@@ -0,0 +1,12 @@
+//! Typed Dolby Vision signaling: the stream level decoder configuration record
+//! and the per-frame RPU metadata.
+
+pub mod config;
+pub use self::config::DecoderConfigurationRecord;
+
+pub mod metadata;
+#[cfg(feature = "ffmpeg_7_0")]
+pub use self::metadata::ExtBlock;
+pub use self::metadata::{ColorMetadata, DataMapping, Metadata, NlqMethod, RpuDataHeader};
+
+mod extensions;
@@ -0,0 +1,790 @@
+//! EXIF metadata, parsed from and serialized to TIFF structured blobs as
+//! carried in `EXIF` frame side data.
+//!
+//! Tags of the primary image (IFD0) and its EXIF and GPS sub-directories are
+//! exposed for editing. Parsed metadata keeps its source blob, so thumbnails,
+//! the interoperability directory and maker notes survive a round trip.
+
+use std::collections::BTreeMap;
+
+#[cfg(all(feature = "codec", feature = "ffmpeg_8_1"))]
+use std::{ffi::CString, mem, ptr, slice};
+
+#[cfg(all(feature = "codec", feature = "ffmpeg_8_1"))]
+use crate::ffi::*;
+use crate::Error;
+#[cfg(all(feature = "codec", feature = "ffmpeg_8_1"))]
+use libc::c_void;
+
+/// Tag of the EXIF sub-directory pointer in IFD0.
+const EXIF_IFD: u16 = 0x8769;
+/// Tag of the GPS sub-directory pointer in IFD0.
+const GPS_IFD: u16 = 0x8825;
+/// Tag of the interoperability sub-directory pointer in the EXIF directory.
+const INTEROP_IFD: u16 = 0xA005;
+
+/// Tag values, one variant per TIFF field type.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Value {
+    Byte(Vec<u8>),
+    Ascii(String),
+    Short(Vec<u16>),
+    Long(Vec<u32>),
+    Rational(Vec<(u32, u32)>),
+    SByte(Vec<i8>),
+    Undefined(Vec<u8>),
+    SShort(Vec<i16>),
+    SLong(Vec<i32>),
+    SRational(Vec<(i32, i32)>),
+    Float(Vec<f32>),
+    Double(Vec<f64>),
+}
+
+impl Value {
+    fn kind(&self) -> u16 {
+        match self {
+            Value::Byte(_) => 1,
+            Value::Ascii(_) => 2,
+            Value::Short(_) => 3,
+            Value::Long(_) => 4,
+            Value::Rational(_) => 5,
+            Value::SByte(_) => 6,
+            Value::Undefined(_) => 7,
+            Value::SShort(_) => 8,
+            Value::SLong(_) => 9,
+            Value::SRational(_) => 10,
+            Value::Float(_) => 11,
+            Value::Double(_) => 12,
+        }
+    }
+
+    /// Number of elements, as stored in the directory entry.
+    fn count(&self) -> usize {
+        match self {
+            Value::Byte(v) | Value::Undefined(v) => v.len(),
+            // NUL terminated
+            Value::Ascii(v) => v.len() + 1,
+            Value::Short(v) => v.len(),
+            Value::Long(v) => v.len(),
+            Value::Rational(v) => v.len(),
+            Value::SByte(v) => v.len(),
+            Value::SShort(v) => v.len(),
+            Value::SLong(v) => v.len(),
+            Value::SRational(v) => v.len(),
+            Value::Float(v) => v.len(),
+            Value::Double(v) => v.len(),
+        }
+    }
+
+    /// Size of the units making up an element, rationals being two.
+    fn unit(&self) -> usize {
+        match self {
+            Value::Short(_) | Value::SShort(_) => 2,
+            Value::Long(_) | Value::SLong(_) | Value::Float(_) => 4,
+            Value::Rational(_) | Value::SRational(_) => 4,
+            Value::Double(_) => 8,
+            _ => 1,
+        }
+    }
+
+    /// Encoding of the elements in the given byte order.
+    fn encode(&self, little_endian: bool) -> Vec<u8> {
+        let mut out = Vec::new();
+
+        match self {
+            Value::Byte(v) | Value::Undefined(v) => out.extend_from_slice(v),
+            Value::Ascii(v) => {
+                out.extend_from_slice(v.as_bytes());
+                out.push(0);
+            }
+            Value::Short(v) => v.iter().for_each(|x| out.extend(x.to_le_bytes())),
+            Value::Long(v) => v.iter().for_each(|x| out.extend(x.to_le_bytes())),
+            Value::Rational(v) => v.iter().for_each(|(n, d)| {
+                out.extend(n.to_le_bytes());
+                out.extend(d.to_le_bytes());
+            }),
+            Value::SByte(v) => v.iter().for_each(|x| out.extend(x.to_le_bytes())),
+            Value::SShort(v) => v.iter().for_each(|x| out.extend(x.to_le_bytes())),
+            Value::SLong(v) => v.iter().for_each(|x| out.extend(x.to_le_bytes())),
+            Value::SRational(v) => v.iter().for_each(|(n, d)| {
+                out.extend(n.to_le_bytes());
+                out.extend(d.to_le_bytes());
+            }),
+            Value::Float(v) => v.iter().for_each(|x| out.extend(x.to_le_bytes())),
+            Value::Double(v) => v.iter().for_each(|x| out.extend(x.to_le_bytes())),
+        }
+
+        if !little_endian {
+            out.chunks_mut(self.unit()).for_each(|unit| unit.reverse());
+        }
+
+        out
+    }
+}
+
+/// Parsed EXIF metadata, as tag maps of the primary image directory and its
+/// EXIF and GPS sub-directories.
+///
+/// Equality only compares the tags.
+#[derive(Clone, Debug, Default)]
+pub struct Exif {
+    pub ifd0: BTreeMap<u16, Value>,
+    pub exif: BTreeMap<u16, Value>,
+    pub gps: BTreeMap<u16, Value>,
+
+    source: Option<Source>,
+}
+
+/// Blob metadata was parsed from, with the tags as parsed.
+#[derive(Clone, Debug)]
+struct Source {
+    data: Vec<u8>,
+    /// Offset of the TIFF header, past any `Exif\0\0` marker.
+    offset: usize,
+    little_endian: bool,
+    tags: [BTreeMap<u16, Value>; 3],
+}
+
+impl Exif {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses a TIFF structured blob, optionally preceded by the `Exif\0\0`
+    /// marker of JPEG APP1 segments.
+    pub fn parse(data: &[u8]) -> Result<Self, Error> {
+        let tiff = data.strip_prefix(b"Exif\0\0").unwrap_or(data);
+        let reader = Reader::new(tiff).ok_or(Error::InvalidData)?;
+
+        let mut exif = Exif::new();
+        let ifd0 = reader.u32(4).ok_or(Error::InvalidData)?;
+
+        for (tag, value) in reader.ifd(ifd0).ok_or(Error::InvalidData)? {
+            match (tag, &value) {
+                (EXIF_IFD, Value::Long(v)) if !v.is_empty() => {
+                    exif.exif = reader
+                        .ifd(v[0])
+                        .ok_or(Error::InvalidData)?
+                        .into_iter()
+                        .filter(|(tag, _)| *tag != INTEROP_IFD)
+                        .collect();
+                }
+                (GPS_IFD, Value::Long(v)) if !v.is_empty() => {
+                    exif.gps = reader
+                        .ifd(v[0])
+                        .ok_or(Error::InvalidData)?
+                        .into_iter()
+                        .collect();
+                }
+                _ => {
+                    exif.ifd0.insert(tag, value);
+                }
+            }
+        }
+
+        exif.source = Some(Source {
+            data: data.to_vec(),
+            offset: data.len() - tiff.len(),
+            little_endian: reader.little_endian,
+            tags: [exif.ifd0.clone(), exif.exif.clone(), exif.gps.clone()],
+        });
+
+        Ok(exif)
+    }
+
+    /// Serializes to a TIFF structured blob without losing any data.
+    ///
+    /// Parsed metadata with unchanged tags is returned as it was parsed, and
+    /// metadata built from scratch is written little endian. Edited tags are
+    /// applied to the source blob through FFmpeg on 8.1+, failing if that would
+    /// require adding an EXIF or GPS sub-directory. Editing parsed metadata
+    /// fails with `ENOSYS` on older versions; see
+    /// [`to_bytes_lossy`](Exif::to_bytes_lossy).
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let Some(source) = &self.source else {
+            return Ok(self.write(true));
+        };
+
+        if source.tags[0] == self.ifd0 && source.tags[1] == self.exif && source.tags[2] == self.gps
+        {
+            return Ok(source.data.clone());
+        }
+
+        #[cfg(all(feature = "codec", feature = "ffmpeg_8_1"))]
+        return unsafe { self.edit(source) };
+
+        #[cfg(not(all(feature = "codec", feature = "ffmpeg_8_1")))]
+        Err(Error::Other {
+            errno: libc::ENOSYS,
+        })
+    }
+
+    /// Serializes the tags to a new TIFF structured blob, in the byte order of
+    /// the source, little endian by default.
+    ///
+    /// Only the primary image directories are written: thumbnails and the
+    /// interoperability directory of parsed metadata are dropped, and maker
+    /// notes relying on offsets into the source blob break.
+    pub fn to_bytes_lossy(&self) -> Vec<u8> {
+        self.write(self.source.as_ref().map_or(true, |s| s.little_endian))
+    }
+
+    fn write(&self, little_endian: bool) -> Vec<u8> {
+        let mut out = if little_endian {
+            b"II\x2a\x00\x08\x00\x00\x00".to_vec()
+        } else {
+            b"MM\x00\x2a\x00\x00\x00\x08".to_vec()
+        };
+
+        let mut ifd0: Vec<(u16, Value)> = self
+            .ifd0
+            .iter()
+            .filter(|(tag, _)| **tag != EXIF_IFD && **tag != GPS_IFD)
+            .map(|(tag, value)| (*tag, value.clone()))
+            .collect();
+
+        // sub-directory pointers are patched once their offsets are known
+        if !self.exif.is_empty() {
+            ifd0.push((EXIF_IFD, Value::Long(vec![0])));
+        }
+        if !self.gps.is_empty() {
+            ifd0.push((GPS_IFD, Value::Long(vec![0])));
+        }
+        ifd0.sort_by_key(|(tag, _)| *tag);
+
+        let pointers = write_ifd(&mut out, &ifd0, little_endian);
+
+        for (tag, entries) in [(EXIF_IFD, &self.exif), (GPS_IFD, &self.gps)] {
+            if entries.is_empty() {
+                continue;
+            }
+
+            let offset = out.len() as u32;
+            let position = pointers[&tag];
+            out[position..position + 4].copy_from_slice(&if little_endian {
+                offset.to_le_bytes()
+            } else {
+                offset.to_be_bytes()
+            });
+
+            let entries: Vec<(u16, Value)> = entries
+                .iter()
+                .filter(|(tag, _)| **tag != INTEROP_IFD)
+                .map(|(tag, value)| (*tag, value.clone()))
+                .collect();
+            write_ifd(&mut out, &entries, little_endian);
+        }
+
+        out
+    }
+
+    /// Applies the tag edits to the source blob with FFmpeg's EXIF API.
+    #[cfg(all(feature = "codec", feature = "ffmpeg_8_1"))]
+    unsafe fn edit(&self, source: &Source) -> Result<Vec<u8>, Error> {
+        let tiff = &source.data[source.offset..];
+        let mut metadata: AVExifMetadata = mem::zeroed();
+
+        match av_exif_parse_buffer(
+            ptr::null_mut(),
+            tiff.as_ptr(),
+            tiff.len(),
+            &mut metadata,
+            AVExifHeaderMode::TIFF_HEADER,
+        ) {
+            e if e < 0 => return Err(Error::from(e)),
+            _ => (),
+        }
+
+        let result = self.apply(source, &mut metadata).and_then(|_| {
+            let mut buffer = ptr::null_mut();
+
+            match av_exif_write(
+                ptr::null_mut(),
+                &metadata,
+                &mut buffer,
+                AVExifHeaderMode::TIFF_HEADER,
+            ) {
+                e if e < 0 => Err(Error::from(e)),
+                _ => {
+                    let mut bytes = source.data[..source.offset].to_vec();
+                    bytes.extend_from_slice(slice::from_raw_parts((*buffer).data, (*buffer).size));
+                    av_buffer_unref(&mut buffer);
+
+                    Ok(bytes)
+                }
+            }
+        });
+
+        av_exif_free(&mut metadata);
+
+        result
+    }
+
+    #[cfg(all(feature = "codec", feature = "ffmpeg_8_1"))]
+    unsafe fn apply(&self, source: &Source, metadata: &mut AVExifMetadata) -> Result<(), Error> {
+        update(metadata, &source.tags[0], &self.ifd0)?;
+
+        for (tag, old, new) in [
+            (EXIF_IFD, &source.tags[1], &self.exif),
+            (GPS_IFD, &source.tags[2], &self.gps),
+        ] {
+            if old == new {
+                continue;
+            }
+
+            // adding a sub-directory is not supported
+            let mut entry = ptr::null_mut();
+            if av_exif_get_entry(ptr::null_mut(), metadata, tag, 0, &mut entry) <= 0
+                || entry.is_null()
+            {
+                return Err(Error::Other {
+                    errno: libc::ENOENT,
+                });
+            }
+
+            update(&mut (*entry).value.ifd, old, new)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl PartialEq for Exif {
+    fn eq(&self, other: &Self) -> bool {
+        self.ifd0 == other.ifd0 && self.exif == other.exif && self.gps == other.gps
+    }
+}
+
+/// Removes the tags of `old` missing from `new` and sets the ones that differ.
+#[cfg(all(feature = "codec", feature = "ffmpeg_8_1"))]
+unsafe fn update(
+    metadata: *mut AVExifMetadata,
+    old: &BTreeMap<u16, Value>,
+    new: &BTreeMap<u16, Value>,
+) -> Result<(), Error> {
+    for tag in old.keys().filter(|tag| !new.contains_key(tag)) {
+        match av_exif_remove_entry(ptr::null_mut(), metadata, *tag, 0) {
+            e if e < 0 => return Err(Error::from(e)),
+            _ => (),
+        }
+    }
+
+    for (tag, value) in new
+        .iter()
+        .filter(|(tag, value)| old.get(tag) != Some(value))
+    {
+        set_entry(metadata, *tag, value)?;
+    }
+
+    Ok(())
+}
+
+/// Sets a tag, converting the value to the element types of `AVExifEntry`.
+#[cfg(all(feature = "codec", feature = "ffmpeg_8_1"))]
+unsafe fn set_entry(metadata: *mut AVExifMetadata, tag: u16, value: &Value) -> Result<(), Error> {
+    let string;
+    let uint: Vec<u64>;
+    let sint: Vec<i64>;
+    let rat: Vec<AVRational>;
+    let dbl: Vec<f64>;
+
+    let (kind, ptr): (AVTiffDataType, *const c_void) = match value {
+        Value::Byte(v) => (AVTiffDataType::BYTE, v.as_ptr() as _),
+        Value::Ascii(v) => {
+            string = CString::new(v.as_str()).map_err(|_| Error::InvalidData)?;
+            (AVTiffDataType::STRING, string.as_ptr() as _)
+        }
+        Value::Short(v) => {
+            uint = v.iter().map(|&x| x as u64).collect();
+            (AVTiffDataType::SHORT, uint.as_ptr() as _)
+        }
+        Value::Long(v) => {
+            uint = v.iter().map(|&x| x as u64).collect();
+            (AVTiffDataType::LONG, uint.as_ptr() as _)
+        }
+        Value::Rational(v) => {
+            // stored as signed rationals, rejecting parts they cannot hold
+            rat = v
+                .iter()
+                .map(|&(num, den)| {
+                    Some(AVRational {
+                        num: i32::try_from(num).ok()?,
+                        den: i32::try_from(den).ok()?,
+                    })
+                })
+                .collect::<Option<_>>()
+                .ok_or(Error::InvalidData)?;
+            (AVTiffDataType::RATIONAL, rat.as_ptr() as _)
+        }
+        Value::SByte(v) => (AVTiffDataType::SBYTE, v.as_ptr() as _),
+        Value::Undefined(v) => (AVTiffDataType::UNDEFINED, v.as_ptr() as _),
+        Value::SShort(v) => {
+            sint = v.iter().map(|&x| x as i64).collect();
+            (AVTiffDataType::SSHORT, sint.as_ptr() as _)
+        }
+        Value::SLong(v) => {
+            sint = v.iter().map(|&x| x as i64).collect();
+            (AVTiffDataType::SLONG, sint.as_ptr() as _)
+        }
+        Value::SRational(v) => {
+            rat = v
+                .iter()
+                .map(|&(num, den)| AVRational { num, den })
+                .collect();
+            (AVTiffDataType::SRATIONAL, rat.as_ptr() as _)
+        }
+        Value::Float(v) => {
+            dbl = v.iter().map(|&x| x as f64).collect();
+            (AVTiffDataType::FLOAT, dbl.as_ptr() as _)
+        }
+        Value::Double(v) => (AVTiffDataType::DOUBLE, v.as_ptr() as _),
+    };
+
+    match av_exif_set_entry(
+        ptr::null_mut(),
+        metadata,
+        tag,
+        kind,
+        value.count() as u32,
+        ptr::null(),
+        0,
+        ptr,
+    ) {
+        e if e < 0 => Err(Error::from(e)),
+        _ => Ok(()),
+    }
+}
+
+/// Appends a directory and its out-of-line values, returning the position of
+/// each entry's value field.
+fn write_ifd(
+    out: &mut Vec<u8>,
+    entries: &[(u16, Value)],
+    little_endian: bool,
+) -> BTreeMap<u16, usize> {
+    let u16_bytes = |v: u16| {
+        if little_endian {
+            v.to_le_bytes()
+        } else {
+            v.to_be_bytes()
+        }
+    };
+    let u32_bytes = |v: u32| {
+        if little_endian {
+            v.to_le_bytes()
+        } else {
+            v.to_be_bytes()
+        }
+    };
+
+    let start = out.len();
+    let mut data = Vec::new();
+    let data_start = start + 2 + entries.len() * 12 + 4;
+    let mut positions = BTreeMap::new();
+
+    out.extend(u16_bytes(entries.len() as u16));
+
+    for (tag, value) in entries {
+        let encoded = value.encode(little_endian);
+
+        out.extend(u16_bytes(*tag));
+        out.extend(u16_bytes(value.kind()));
+        out.extend(u32_bytes(value.count() as u32));
+        positions.insert(*tag, out.len());
+
+        if encoded.len() <= 4 {
+            out.extend(&encoded);
+            out.resize(out.len() + 4 - encoded.len(), 0);
+        } else {
+            out.extend(u32_bytes((data_start + data.len()) as u32));
+            data.extend(&encoded);
+
+            // values start on word boundaries
+            if data.len() % 2 == 1 {
+                data.push(0);
+            }
+        }
+    }
+
+    // no next directory
+    out.extend(u32_bytes(0));
+    out.extend(data);
+
+    positions
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    little_endian: bool,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Option<Self> {
+        let little_endian = match data.get(..4)? {
+            b"II\x2a\x00" => true,
+            b"MM\x00\x2a" => false,
+            _ => return None,
+        };
+
+        Some(Reader {
+            data,
+            little_endian,
+        })
+    }
+
+    fn bytes<const N: usize>(&self, offset: usize) -> Option<[u8; N]> {
+        let mut bytes: [u8; N] = self
+            .data
+            .get(offset..offset.checked_add(N)?)?
+            .try_into()
+            .ok()?;
+
+        if !self.little_endian {
+            bytes.reverse();
+        }
+
+        Some(bytes)
+    }
+
+    fn u16(&self, offset: usize) -> Option<u16> {
+        self.bytes(offset).map(u16::from_le_bytes)
+    }
+
+    fn u32(&self, offset: usize) -> Option<u32> {
+        self.bytes(offset).map(u32::from_le_bytes)
+    }
+
+    fn ifd(&self, offset: u32) -> Option<Vec<(u16, Value)>> {
+        let offset = offset as usize;
+        let count = self.u16(offset)? as usize;
+
+        (0..count)
+            .map(|i| {
+                let entry = offset + 2 + i * 12;
+
+                Some((self.u16(entry)?, self.value(entry)?))
+            })
+            .collect()
+    }
+
+    fn value(&self, entry: usize) -> Option<Value> {
+        let kind = self.u16(entry + 2)?;
+        let count = self.u32(entry + 4)? as usize;
+
+        let size = match kind {
+            1 | 2 | 6 | 7 => 1,
+            3 | 8 => 2,
+            4 | 9 | 11 => 4,
+            5 | 10 | 12 => 8,
+            _ => return None,
+        };
+        let length = count.checked_mul(size)?;
+
+        let start = if length <= 4 {
+            entry + 8
+        } else {
+            self.u32(entry + 8)? as usize
+        };
+        self.data.get(start..start.checked_add(length)?)?;
+
+        let elements = |size: usize| (0..count).map(move |i| start + i * size);
+
+        Some(match kind {
+            1 => Value::Byte(self.data[start..start + length].to_vec()),
+            2 => {
+                let bytes = &self.data[start..start + length];
+                let end = bytes.iter().position(|&c| c == 0).unwrap_or(bytes.len());
+
+                Value::Ascii(String::from_utf8_lossy(&bytes[..end]).into_owned())
+            }
+            3 => Value::Short(elements(2).map(|o| self.u16(o)).collect::<Option<_>>()?),
+            4 => Value::Long(elements(4).map(|o| self.u32(o)).collect::<Option<_>>()?),
+            5 => Value::Rational(
+                elements(8)
+                    .map(|o| Some((self.u32(o)?, self.u32(o + 4)?)))
+                    .collect::<Option<_>>()?,
+            ),
+            6 => Value::SByte(
+                self.data[start..start + length]
+                    .iter()
+                    .map(|&b| b as i8)
+                    .collect(),
+            ),
+            7 => Value::Undefined(self.data[start..start + length].to_vec()),
+            8 => Value::SShort(
+                elements(2)
+                    .map(|o| self.u16(o).map(|v| v as i16))
+                    .collect::<Option<_>>()?,
+            ),
+            9 => Value::SLong(
+                elements(4)
+                    .map(|o| self.u32(o).map(|v| v as i32))
+                    .collect::<Option<_>>()?,
+            ),
+            10 => Value::SRational(
+                elements(8)
+                    .map(|o| Some((self.u32(o)? as i32, self.u32(o + 4)? as i32)))
+                    .collect::<Option<_>>()?,
+            ),
+            11 => Value::Float(
+                elements(4)
+                    .map(|o| self.u32(o).map(f32::from_bits))
+                    .collect::<Option<_>>()?,
+            ),
+            _ => Value::Double(
+                elements(8)
+                    .map(|o| self.bytes(o).map(f64::from_le_bytes))
+                    .collect::<Option<_>>()?,
+            ),
+        })
+    }
+}
+
+#[cfg(feature = "ffmpeg_8_1")]
+impl crate::frame::Video {
+    /// Parses the EXIF side data, returning `None` if there is none or it is
+    /// malformed.
+    pub fn exif(&self) -> Option<Exif> {
+        Exif::parse(self.side_data(crate::frame::side_data::Type::EXIF)?.data()).ok()
+    }
+
+    /// Attaches EXIF metadata, replacing any existing entry.
+    pub fn set_exif(&mut self, value: &Exif) -> Result<(), Error> {
+        use crate::frame::side_data::Type;
+
+        let bytes = value.to_bytes()?;
+        self.remove_side_data(Type::EXIF);
+
+        let mut side_data = self
+            .new_side_data(Type::EXIF, bytes.len())
+            .ok_or(Error::Other {
+                errno: libc::ENOMEM,
+            })?;
+
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                bytes.as_ptr(),
+                (*side_data.as_mut_ptr()).data,
+                bytes.len(),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Exif {
+        let mut exif = Exif::new();
+        exif.ifd0
+            .insert(0x010F, Value::Ascii("Camera Maker".to_owned()));
+        exif.ifd0.insert(0x0112, Value::Short(vec![6]));
+        exif.ifd0.insert(0x011A, Value::Rational(vec![(72, 1)]));
+        exif.exif.insert(0x9000, Value::Undefined(b"0232".to_vec()));
+        exif.exif.insert(0x9204, Value::SRational(vec![(-1, 3)]));
+        exif.gps
+            .insert(0x0002, Value::Rational(vec![(48, 1), (51, 1), (2980, 100)]));
+
+        exif
+    }
+
+    #[test]
+    fn round_trip() {
+        let exif = sample();
+        let bytes = exif.to_bytes().unwrap();
+
+        assert_eq!(&bytes[..8], b"II\x2a\x00\x08\x00\x00\x00");
+        assert_eq!(Exif::parse(&bytes).unwrap(), exif);
+    }
+
+    #[test]
+    fn big_endian() {
+        // MM header, IFD0 at 8 with a single SHORT orientation entry
+        let bytes = b"MM\x00\x2a\x00\x00\x00\x08\
+                      \x00\x01\
+                      \x01\x12\x00\x03\x00\x00\x00\x01\x00\x03\x00\x00\
+                      \x00\x00\x00\x00";
+
+        let exif = Exif::parse(bytes).unwrap();
+        assert_eq!(exif.ifd0.get(&0x0112), Some(&Value::Short(vec![3])));
+    }
+
+    #[test]
+    fn unchanged() {
+        // big endian, IFD0 with a single orientation entry and a link to IFD1
+        let bytes = b"Exif\0\0MM\x00\x2a\x00\x00\x00\x08\
+                      \x00\x01\
+                      \x01\x12\x00\x03\x00\x00\x00\x01\x00\x03\x00\x00\
+                      \x00\x00\x00\x1a\
+                      \x00\x00\x00\x00\x00\x00";
+
+        let exif = Exif::parse(bytes).unwrap();
+        assert_eq!(exif.to_bytes().unwrap(), bytes);
+
+        let mut edited = exif.clone();
+        edited.ifd0.insert(0x0112, Value::Short(vec![1]));
+        edited
+            .ifd0
+            .insert(0x010F, Value::Ascii("Camera Maker".to_owned()));
+
+        match edited.to_bytes() {
+            Ok(bytes) => assert_eq!(Exif::parse(&bytes).unwrap(), edited),
+            Err(_) => assert!(cfg!(not(all(feature = "codec", feature = "ffmpeg_8_1")))),
+        }
+    }
+
+    #[test]
+    fn lossy() {
+        // big endian, IFD0 with no entries and a link to an empty IFD1
+        let bytes = b"MM\x00\x2a\x00\x00\x00\x08\
+                      \x00\x00\x00\x00\x00\x0e\
+                      \x00\x00\x00\x00\x00\x00";
+        let mut exif = Exif::parse(bytes).unwrap();
+        exif.ifd0 = sample().ifd0;
+        exif.exif = sample().exif;
+
+        // adding a sub-directory cannot be done without rewriting the blob
+        assert!(exif.to_bytes().is_err());
+
+        let lossy = exif.to_bytes_lossy();
+        assert_eq!(&lossy[..4], b"MM\x00\x2a");
+        assert_eq!(Exif::parse(&lossy).unwrap(), exif);
+
+        // the link to IFD1 is gone
+        let reader = Reader::new(&lossy).unwrap();
+        let count = reader.u16(8).unwrap() as usize;
+        assert_eq!(reader.u32(8 + 2 + count * 12), Some(0));
+    }
+
+    #[cfg(feature = "ffmpeg_8_1")]
+    #[test]
+    fn frame() {
+        let mut frame = crate::frame::Video::new(crate::format::Pixel::RGB24, 16, 16);
+        assert!(frame.exif().is_none());
+
+        frame.set_exif(&sample()).unwrap();
+        assert_eq!(frame.exif().unwrap(), sample());
+
+        let parsed = frame.exif().unwrap();
+        let mut copy = crate::frame::Video::new(crate::format::Pixel::RGB24, 16, 16);
+        copy.set_exif(&parsed).unwrap();
+        assert_eq!(
+            copy.side_data(crate::frame::side_data::Type::EXIF)
+                .unwrap()
+                .data(),
+            frame
+                .side_data(crate::frame::side_data::Type::EXIF)
+                .unwrap()
+                .data()
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(Exif::parse(b"not exif").is_err());
+        assert!(Exif::parse(b"II\x2a\x00\xff\x00\x00\x00").is_err());
+    }
+}
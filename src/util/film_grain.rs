@@ -0,0 +1,317 @@
+//! Film grain synthesis parameters (AV1 and H.274), as found in
+//! `FILM_GRAIN_PARAMS` frame side data.
+//!
+//! Decoders only attach them when grain application is disabled, see
+//! `decoder::ExportSideData::FILM_GRAIN`.
+
+use std::mem;
+
+use crate::ffi::*;
+use crate::frame::{side_data::Type, Frame};
+use crate::{utils, Error};
+
+/// AV1 film grain parameters, see section 6.8.20 of the AV1 specification.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Av1Params {
+    /// Up to 14 (value, scaling) points of the luma scaling function.
+    pub y_points: Vec<[u8; 2]>,
+    pub chroma_scaling_from_luma: bool,
+    /// Up to 10 (value, scaling) points of the Cb and Cr scaling functions.
+    pub uv_points: [Vec<[u8; 2]>; 2],
+    pub scaling_shift: i32,
+    pub ar_coeff_lag: i32,
+    pub ar_coeffs_y: [i8; 24],
+    pub ar_coeffs_uv: [[i8; 25]; 2],
+    pub ar_coeff_shift: i32,
+    pub grain_scale_shift: i32,
+    pub uv_mult: [i32; 2],
+    pub uv_mult_luma: [i32; 2],
+    pub uv_offset: [i32; 2],
+    pub overlap: bool,
+    pub limit_output_range: bool,
+}
+
+/// H.274 film grain characteristics SEI parameters.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct H274Params {
+    pub model_id: i32,
+    pub blending_mode_id: i32,
+    pub log2_scale_factor: i32,
+    /// Grain model of each color component, `None` if the component has no grain.
+    pub components: [Option<H274Component>; 3],
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct H274Component {
+    /// Number of used entries in each interval's `model_values`, at most 6.
+    pub num_model_values: u8,
+    /// Up to 256 intensity intervals.
+    pub intervals: Vec<H274Interval>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct H274Interval {
+    pub lower_bound: u8,
+    pub upper_bound: u8,
+    pub model_values: [i16; 6],
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Grain {
+    Av1(Av1Params),
+    H274(H274Params),
+}
+
+/// Film grain parameters. The target frame format fields added in FFmpeg 6.1
+/// are left unset, meaning the parameters apply to any frame.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct FilmGrainParams {
+    /// Seed of the grain pseudo-random number generator.
+    pub seed: u64,
+    pub grain: Grain,
+}
+
+impl FilmGrainParams {
+    /// Converts raw parameters, returning `None` if their type is unknown.
+    pub fn from_raw(value: &AVFilmGrainParams) -> Option<Self> {
+        let grain = match value.type_ {
+            AVFilmGrainParamsType::AV1 => Grain::Av1(unsafe { av1_from_raw(&value.codec.aom) }),
+            AVFilmGrainParamsType::H274 => Grain::H274(unsafe { h274_from_raw(&value.codec.h274) }),
+            _ => return None,
+        };
+
+        Some(FilmGrainParams {
+            seed: value.seed,
+            grain,
+        })
+    }
+
+    /// Converts to raw parameters, failing if a list exceeds FFmpeg's limits.
+    pub fn to_raw(&self) -> Result<AVFilmGrainParams, Error> {
+        let mut raw: AVFilmGrainParams = unsafe { mem::zeroed() };
+        raw.seed = self.seed;
+
+        match &self.grain {
+            Grain::Av1(params) => {
+                raw.type_ = AVFilmGrainParamsType::AV1;
+                raw.codec.aom = av1_to_raw(params)?;
+            }
+            Grain::H274(params) => {
+                raw.type_ = AVFilmGrainParamsType::H274;
+                raw.codec.h274 = h274_to_raw(params)?;
+            }
+        }
+
+        Ok(raw)
+    }
+}
+
+fn invalid() -> Error {
+    Error::Other {
+        errno: libc::EINVAL,
+    }
+}
+
+/// Copies `values` into the start of `array`, returning their count.
+fn copy_points<T: Copy>(array: &mut [T], values: &[T]) -> Result<i32, Error> {
+    array
+        .get_mut(..values.len())
+        .ok_or_else(invalid)?
+        .copy_from_slice(values);
+
+    Ok(values.len() as i32)
+}
+
+fn av1_from_raw(value: &AVFilmGrainAOMParams) -> Av1Params {
+    let points = |points: &[[u8; 2]], count: i32| {
+        points[..(count.max(0) as usize).min(points.len())].to_vec()
+    };
+
+    Av1Params {
+        y_points: points(&value.y_points, value.num_y_points),
+        chroma_scaling_from_luma: value.chroma_scaling_from_luma != 0,
+        uv_points: [
+            points(&value.uv_points[0], value.num_uv_points[0]),
+            points(&value.uv_points[1], value.num_uv_points[1]),
+        ],
+        scaling_shift: value.scaling_shift,
+        ar_coeff_lag: value.ar_coeff_lag,
+        ar_coeffs_y: value.ar_coeffs_y,
+        ar_coeffs_uv: value.ar_coeffs_uv,
+        ar_coeff_shift: value.ar_coeff_shift,
+        grain_scale_shift: value.grain_scale_shift,
+        uv_mult: value.uv_mult,
+        uv_mult_luma: value.uv_mult_luma,
+        uv_offset: value.uv_offset,
+        overlap: value.overlap_flag != 0,
+        limit_output_range: value.limit_output_range != 0,
+    }
+}
+
+fn av1_to_raw(value: &Av1Params) -> Result<AVFilmGrainAOMParams, Error> {
+    let mut raw: AVFilmGrainAOMParams = unsafe { mem::zeroed() };
+
+    raw.num_y_points = copy_points(&mut raw.y_points, &value.y_points)?;
+    raw.chroma_scaling_from_luma = value.chroma_scaling_from_luma as i32;
+    raw.num_uv_points[0] = copy_points(&mut raw.uv_points[0], &value.uv_points[0])?;
+    raw.num_uv_points[1] = copy_points(&mut raw.uv_points[1], &value.uv_points[1])?;
+    raw.scaling_shift = value.scaling_shift;
+    raw.ar_coeff_lag = value.ar_coeff_lag;
+    raw.ar_coeffs_y = value.ar_coeffs_y;
+    raw.ar_coeffs_uv = value.ar_coeffs_uv;
+    raw.ar_coeff_shift = value.ar_coeff_shift;
+    raw.grain_scale_shift = value.grain_scale_shift;
+    raw.uv_mult = value.uv_mult;
+    raw.uv_mult_luma = value.uv_mult_luma;
+    raw.uv_offset = value.uv_offset;
+    raw.overlap_flag = value.overlap as i32;
+    raw.limit_output_range = value.limit_output_range as i32;
+
+    Ok(raw)
+}
+
+fn h274_from_raw(value: &AVFilmGrainH274Params) -> H274Params {
+    let component = |c: usize| {
+        (value.component_model_present[c] != 0).then(|| {
+            let count = value.num_intensity_intervals[c] as usize;
+
+            H274Component {
+                num_model_values: value.num_model_values[c],
+                intervals: (0..count.min(256))
+                    .map(|i| H274Interval {
+                        lower_bound: value.intensity_interval_lower_bound[c][i],
+                        upper_bound: value.intensity_interval_upper_bound[c][i],
+                        model_values: value.comp_model_value[c][i],
+                    })
+                    .collect(),
+            }
+        })
+    };
+
+    H274Params {
+        model_id: value.model_id,
+        blending_mode_id: value.blending_mode_id,
+        log2_scale_factor: value.log2_scale_factor,
+        components: [component(0), component(1), component(2)],
+    }
+}
+
+fn h274_to_raw(value: &H274Params) -> Result<AVFilmGrainH274Params, Error> {
+    let mut raw: AVFilmGrainH274Params = unsafe { mem::zeroed() };
+
+    raw.model_id = value.model_id;
+    raw.blending_mode_id = value.blending_mode_id;
+    raw.log2_scale_factor = value.log2_scale_factor;
+
+    for (c, component) in value.components.iter().enumerate() {
+        let Some(component) = component else {
+            continue;
+        };
+
+        if component.num_model_values > 6 || component.intervals.len() > 256 {
+            return Err(invalid());
+        }
+
+        raw.component_model_present[c] = 1;
+        raw.num_model_values[c] = component.num_model_values;
+        raw.num_intensity_intervals[c] = component.intervals.len() as u16;
+
+        for (i, interval) in component.intervals.iter().enumerate() {
+            raw.intensity_interval_lower_bound[c][i] = interval.lower_bound;
+            raw.intensity_interval_upper_bound[c][i] = interval.upper_bound;
+            raw.comp_model_value[c][i] = interval.model_values;
+        }
+    }
+
+    Ok(raw)
+}
+
+impl Frame {
+    pub fn film_grain_params(&self) -> Option<FilmGrainParams> {
+        let side_data = self.side_data(Type::FILM_GRAIN_PARAMS)?;
+        let raw = unsafe { utils::read_struct::<AVFilmGrainParams>(side_data.data())? };
+
+        FilmGrainParams::from_raw(&raw)
+    }
+
+    /// Attaches film grain parameters, replacing any existing entries.
+    pub fn set_film_grain_params(&mut self, value: &FilmGrainParams) -> Result<(), Error> {
+        let raw = value.to_raw()?;
+
+        self.remove_side_data(Type::FILM_GRAIN_PARAMS);
+
+        unsafe {
+            let ptr = av_film_grain_params_create_side_data(self.as_mut_ptr());
+
+            if ptr.is_null() {
+                return Err(Error::Other {
+                    errno: libc::ENOMEM,
+                });
+            }
+
+            *ptr = raw;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let av1 = FilmGrainParams {
+            seed: 1234,
+            grain: Grain::Av1(Av1Params {
+                y_points: vec![[0, 20], [255, 40]],
+                uv_points: [vec![[128, 10]], Vec::new()],
+                scaling_shift: 8,
+                ar_coeff_lag: 1,
+                overlap: true,
+                ..Default::default()
+            }),
+        };
+        assert_eq!(
+            FilmGrainParams::from_raw(&av1.to_raw().unwrap()).unwrap(),
+            av1
+        );
+
+        let mut component = H274Component {
+            num_model_values: 1,
+            ..Default::default()
+        };
+        component.intervals.push(H274Interval {
+            lower_bound: 16,
+            upper_bound: 235,
+            model_values: [100, 0, 0, 0, 0, 0],
+        });
+
+        let h274 = FilmGrainParams {
+            seed: 42,
+            grain: Grain::H274(H274Params {
+                log2_scale_factor: 4,
+                components: [Some(component), None, None],
+                ..Default::default()
+            }),
+        };
+        assert_eq!(
+            FilmGrainParams::from_raw(&h274.to_raw().unwrap()).unwrap(),
+            h274
+        );
+    }
+
+    #[test]
+    fn limits() {
+        let params = FilmGrainParams {
+            seed: 0,
+            grain: Grain::Av1(Av1Params {
+                y_points: vec![[0, 0]; 15],
+                ..Default::default()
+            }),
+        };
+
+        assert!(params.to_raw().is_err());
+    }
+}
@@ -0,0 +1,28 @@
+use crate::ffi::*;
+
+/// Content light level information (CTA-861.3), as found in HDR10 streams.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct ContentLightLevel {
+    /// Maximum content light level (MaxCLL), in cd/m².
+    pub max_cll: u32,
+    /// Maximum frame-average light level (MaxFALL), in cd/m².
+    pub max_fall: u32,
+}
+
+impl From<AVContentLightMetadata> for ContentLightLevel {
+    fn from(value: AVContentLightMetadata) -> Self {
+        ContentLightLevel {
+            max_cll: value.MaxCLL as u32,
+            max_fall: value.MaxFALL as u32,
+        }
+    }
+}
+
+impl From<ContentLightLevel> for AVContentLightMetadata {
+    fn from(value: ContentLightLevel) -> Self {
+        AVContentLightMetadata {
+            MaxCLL: value.max_cll as _,
+            MaxFALL: value.max_fall as _,
+        }
+    }
+}
@@ -0,0 +1,260 @@
+use std::mem;
+
+use crate::ffi::*;
+use crate::{Error, Rational};
+
+/// ITU-T T.35 country code of the United States, used by all HDR10+ metadata.
+const COUNTRY_CODE_US: u8 = 0xB5;
+
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum OverlapProcess {
+    WeightedAveraging,
+    Layering,
+}
+
+impl From<AVHDRPlusOverlapProcessOption> for OverlapProcess {
+    fn from(value: AVHDRPlusOverlapProcessOption) -> Self {
+        use AVHDRPlusOverlapProcessOption as AV;
+
+        match value {
+            AV::WEIGHTED_AVERAGING => OverlapProcess::WeightedAveraging,
+            AV::LAYERING => OverlapProcess::Layering,
+
+            _ => unimplemented!(),
+        }
+    }
+}
+
+impl From<OverlapProcess> for AVHDRPlusOverlapProcessOption {
+    fn from(value: OverlapProcess) -> Self {
+        use AVHDRPlusOverlapProcessOption as AV;
+
+        match value {
+            OverlapProcess::WeightedAveraging => AV::WEIGHTED_AVERAGING,
+            OverlapProcess::Layering => AV::LAYERING,
+        }
+    }
+}
+
+/// HDR10+ dynamic metadata (SMPTE ST 2094-40 application 4).
+#[derive(Clone)]
+pub struct DynamicHdrPlus(AVDynamicHDRPlus);
+
+impl DynamicHdrPlus {
+    /// Creates metadata with a single (full frame) processing window and all
+    /// other values zeroed.
+    pub fn new() -> Self {
+        let mut raw: AVDynamicHDRPlus = unsafe { mem::zeroed() };
+        raw.itu_t_t35_country_code = COUNTRY_CODE_US;
+        raw.application_version = 1;
+        raw.num_windows = 1;
+
+        DynamicHdrPlus(raw)
+    }
+
+    /// Parses the SMPTE ST 2094-40 payload of an ITU-T T.35 message, i.e. the
+    /// bytes following the country, provider and application identifiers.
+    pub fn from_t35(data: &[u8]) -> Result<Self, Error> {
+        let mut metadata = Self::new();
+
+        unsafe {
+            match av_dynamic_hdr_plus_from_t35(&mut metadata.0, data.as_ptr(), data.len() as _) {
+                e if e < 0 => Err(Error::from(e)),
+                _ => Ok(metadata),
+            }
+        }
+    }
+
+    /// Serializes the metadata into a complete ITU-T T.35 message, including the
+    /// country, provider and application identifiers.
+    #[cfg(feature = "ffmpeg_6_0")]
+    pub fn to_t35(&self) -> Result<Vec<u8>, Error> {
+        unsafe {
+            let mut data = std::ptr::null_mut();
+            let mut size = 0;
+
+            match av_dynamic_hdr_plus_to_t35(&self.0, &mut data, &mut size) {
+                e if e < 0 => Err(Error::from(e)),
+                _ => {
+                    let bytes = std::slice::from_raw_parts(data, size as usize).to_vec();
+                    av_free(data as *mut _);
+
+                    Ok(bytes)
+                }
+            }
+        }
+    }
+
+    #[inline(always)]
+    pub fn as_ptr(&self) -> *const AVDynamicHDRPlus {
+        &self.0
+    }
+
+    #[inline(always)]
+    pub fn as_mut_ptr(&mut self) -> *mut AVDynamicHDRPlus {
+        &mut self.0
+    }
+}
+
+impl DynamicHdrPlus {
+    pub fn country_code(&self) -> u8 {
+        self.0.itu_t_t35_country_code
+    }
+
+    pub fn application_version(&self) -> u8 {
+        self.0.application_version
+    }
+
+    pub fn set_application_version(&mut self, value: u8) {
+        self.0.application_version = value;
+    }
+
+    /// Processing windows; the first one always covers the whole frame.
+    pub fn windows(&self) -> impl ExactSizeIterator<Item = Window<'_>> {
+        let count = (self.0.num_windows as usize).min(self.0.params.len());
+
+        self.0.params[..count].iter().map(Window)
+    }
+
+    /// Nominal maximum luminance of the targeted system display, in cd/m².
+    pub fn targeted_system_display_maximum_luminance(&self) -> Rational {
+        self.0.targeted_system_display_maximum_luminance.into()
+    }
+
+    pub fn set_targeted_system_display_maximum_luminance(&mut self, value: Rational) {
+        self.0.targeted_system_display_maximum_luminance = value.into();
+    }
+
+    /// Normalized actual peak luminance of the targeted system display, as rows of columns.
+    pub fn targeted_system_display_actual_peak_luminance(&self) -> Option<Vec<Vec<Rational>>> {
+        (self.0.targeted_system_display_actual_peak_luminance_flag != 0).then(|| {
+            matrix(
+                &self.0.targeted_system_display_actual_peak_luminance,
+                self.0
+                    .num_rows_targeted_system_display_actual_peak_luminance,
+                self.0
+                    .num_cols_targeted_system_display_actual_peak_luminance,
+            )
+        })
+    }
+
+    /// Normalized actual peak luminance of the mastering display, as rows of columns.
+    pub fn mastering_display_actual_peak_luminance(&self) -> Option<Vec<Vec<Rational>>> {
+        (self.0.mastering_display_actual_peak_luminance_flag != 0).then(|| {
+            matrix(
+                &self.0.mastering_display_actual_peak_luminance,
+                self.0.num_rows_mastering_display_actual_peak_luminance,
+                self.0.num_cols_mastering_display_actual_peak_luminance,
+            )
+        })
+    }
+}
+
+impl Default for DynamicHdrPlus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<AVDynamicHDRPlus> for DynamicHdrPlus {
+    fn from(value: AVDynamicHDRPlus) -> Self {
+        DynamicHdrPlus(value)
+    }
+}
+
+impl From<DynamicHdrPlus> for AVDynamicHDRPlus {
+    fn from(value: DynamicHdrPlus) -> Self {
+        value.0
+    }
+}
+
+fn matrix<const R: usize, const C: usize>(
+    values: &[[AVRational; C]; R],
+    rows: u8,
+    cols: u8,
+) -> Vec<Vec<Rational>> {
+    values[..(rows as usize).min(R)]
+        .iter()
+        .map(|row| {
+            row[..(cols as usize).min(C)]
+                .iter()
+                .map(|&v| v.into())
+                .collect()
+        })
+        .collect()
+}
+
+/// Color transform parameters of a single HDR10+ processing window.
+#[derive(Copy, Clone)]
+pub struct Window<'a>(&'a AVHDRPlusColorTransformParams);
+
+impl<'a> Window<'a> {
+    /// Relative (x, y) position of the top left corner of the window.
+    pub fn upper_left_corner(&self) -> (Rational, Rational) {
+        (
+            self.0.window_upper_left_corner_x.into(),
+            self.0.window_upper_left_corner_y.into(),
+        )
+    }
+
+    /// Relative (x, y) position of the bottom right corner of the window.
+    pub fn lower_right_corner(&self) -> (Rational, Rational) {
+        (
+            self.0.window_lower_right_corner_x.into(),
+            self.0.window_lower_right_corner_y.into(),
+        )
+    }
+
+    pub fn overlap_process(&self) -> OverlapProcess {
+        self.0.overlap_process_option.into()
+    }
+
+    /// Maximum of each linearized R, G and B component, normalized to 100000 cd/m².
+    pub fn maxscl(&self) -> [Rational; 3] {
+        self.0.maxscl.map(Rational::from)
+    }
+
+    pub fn average_maxrgb(&self) -> Rational {
+        self.0.average_maxrgb.into()
+    }
+
+    /// (percentage, percentile) pairs of the maxRGB distribution.
+    pub fn distribution_maxrgb(&self) -> Vec<(u8, Rational)> {
+        let count = (self.0.num_distribution_maxrgb_percentiles as usize)
+            .min(self.0.distribution_maxrgb.len());
+
+        self.0.distribution_maxrgb[..count]
+            .iter()
+            .map(|p| (p.percentage, p.percentile.into()))
+            .collect()
+    }
+
+    pub fn fraction_bright_pixels(&self) -> Rational {
+        self.0.fraction_bright_pixels.into()
+    }
+
+    /// Knee point (x, y) of the tone mapping curve, if tone mapping is signaled.
+    pub fn knee_point(&self) -> Option<(Rational, Rational)> {
+        (self.0.tone_mapping_flag != 0)
+            .then(|| (self.0.knee_point_x.into(), self.0.knee_point_y.into()))
+    }
+
+    /// Bezier curve anchors of the tone mapping curve; empty unless tone mapping is signaled.
+    pub fn bezier_curve_anchors(&self) -> Vec<Rational> {
+        if self.0.tone_mapping_flag == 0 {
+            return Vec::new();
+        }
+
+        let count =
+            (self.0.num_bezier_curve_anchors as usize).min(self.0.bezier_curve_anchors.len());
+
+        self.0.bezier_curve_anchors[..count]
+            .iter()
+            .map(|&v| v.into())
+            .collect()
+    }
+
+    pub fn color_saturation_weight(&self) -> Option<Rational> {
+        (self.0.color_saturation_mapping_flag != 0).then(|| self.0.color_saturation_weight.into())
+    }
+}
@@ -0,0 +1,168 @@
+use super::{ContentLightLevel, DynamicHdrPlus, MasteringDisplayMetadata};
+use crate::ffi::*;
+use crate::frame::{side_data::Type, Frame};
+use crate::utils;
+use crate::Error;
+
+#[cfg(feature = "codec")]
+use crate::codec::packet;
+#[cfg(all(feature = "codec", feature = "ffmpeg_6_1"))]
+use crate::codec::{Parameters, ParametersMut, ParametersRef};
+#[cfg(all(feature = "codec", feature = "ffmpeg_6_1"))]
+use crate::macros::impl_for_many;
+
+impl Frame {
+    pub fn mastering_display_metadata(&self) -> Option<MasteringDisplayMetadata> {
+        let side_data = self.side_data(Type::MasteringDisplayMetadata)?;
+
+        unsafe { utils::read_struct::<AVMasteringDisplayMetadata>(side_data.data()) }
+            .map(MasteringDisplayMetadata::from)
+    }
+
+    /// Attaches mastering display metadata, replacing any existing entry.
+    pub fn set_mastering_display_metadata(
+        &mut self,
+        value: MasteringDisplayMetadata,
+    ) -> Result<(), Error> {
+        self.remove_side_data(Type::MasteringDisplayMetadata);
+
+        unsafe {
+            let ptr = av_mastering_display_metadata_create_side_data(self.as_mut_ptr());
+
+            if ptr.is_null() {
+                return Err(Error::Other {
+                    errno: libc::ENOMEM,
+                });
+            }
+
+            *ptr = value.into();
+        }
+
+        Ok(())
+    }
+
+    pub fn content_light_level(&self) -> Option<ContentLightLevel> {
+        let side_data = self.side_data(Type::ContentLightLevel)?;
+
+        unsafe { utils::read_struct::<AVContentLightMetadata>(side_data.data()) }
+            .map(ContentLightLevel::from)
+    }
+
+    /// Attaches content light level information, replacing any existing entry.
+    pub fn set_content_light_level(&mut self, value: ContentLightLevel) -> Result<(), Error> {
+        self.remove_side_data(Type::ContentLightLevel);
+
+        unsafe {
+            let ptr = av_content_light_metadata_create_side_data(self.as_mut_ptr());
+
+            if ptr.is_null() {
+                return Err(Error::Other {
+                    errno: libc::ENOMEM,
+                });
+            }
+
+            *ptr = value.into();
+        }
+
+        Ok(())
+    }
+
+    pub fn dynamic_hdr_plus(&self) -> Option<DynamicHdrPlus> {
+        let side_data = self.side_data(Type::DYNAMIC_HDR_PLUS)?;
+
+        unsafe { utils::read_struct::<AVDynamicHDRPlus>(side_data.data()) }
+            .map(DynamicHdrPlus::from)
+    }
+
+    /// Attaches HDR10+ dynamic metadata, replacing any existing entry.
+    pub fn set_dynamic_hdr_plus(&mut self, value: &DynamicHdrPlus) -> Result<(), Error> {
+        self.remove_side_data(Type::DYNAMIC_HDR_PLUS);
+
+        unsafe {
+            let ptr = av_dynamic_hdr_plus_create_side_data(self.as_mut_ptr());
+
+            if ptr.is_null() {
+                return Err(Error::Other {
+                    errno: libc::ENOMEM,
+                });
+            }
+
+            *ptr = *value.as_ptr();
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "codec")]
+impl<'a> packet::SideData<'a> {
+    /// Returns the mastering display metadata if this is a `MasteringDisplayMetadata` entry.
+    pub fn mastering_display_metadata(&self) -> Option<MasteringDisplayMetadata> {
+        unsafe {
+            self.payload::<AVMasteringDisplayMetadata>(
+                packet::side_data::Type::MasteringDisplayMetadata,
+            )
+        }
+        .map(MasteringDisplayMetadata::from)
+    }
+
+    /// Returns the content light level if this is a `ContentLightLevel` entry.
+    pub fn content_light_level(&self) -> Option<ContentLightLevel> {
+        unsafe {
+            self.payload::<AVContentLightMetadata>(packet::side_data::Type::ContentLightLevel)
+        }
+        .map(ContentLightLevel::from)
+    }
+
+    /// Returns the HDR10+ metadata if this is a `DYNAMIC_HDR10_PLUS` entry.
+    pub fn dynamic_hdr_plus(&self) -> Option<DynamicHdrPlus> {
+        unsafe { self.payload::<AVDynamicHDRPlus>(packet::side_data::Type::DYNAMIC_HDR10_PLUS) }
+            .map(DynamicHdrPlus::from)
+    }
+}
+
+#[cfg(all(feature = "codec", feature = "ffmpeg_6_1"))]
+impl_for_many! {
+    impl for Parameters, ParametersRef<'p>, ParametersMut<'p> {
+        pub fn mastering_display_metadata(&self) -> Option<MasteringDisplayMetadata> {
+            self.coded_side_data(packet::side_data::Type::MasteringDisplayMetadata)?
+                .mastering_display_metadata()
+        }
+
+        pub fn content_light_level(&self) -> Option<ContentLightLevel> {
+            self.coded_side_data(packet::side_data::Type::ContentLightLevel)?
+                .content_light_level()
+        }
+    }
+}
+
+#[cfg(all(feature = "codec", feature = "ffmpeg_6_1"))]
+impl_for_many! {
+    impl for Parameters, ParametersMut<'p> {
+        /// Attaches mastering display metadata, replacing any existing entry.
+        pub fn set_mastering_display_metadata(&mut self, value: MasteringDisplayMetadata) -> Result<(), Error> {
+            unsafe {
+                let ptr = self.new_coded_side_data(
+                    packet::side_data::Type::MasteringDisplayMetadata,
+                    std::mem::size_of::<AVMasteringDisplayMetadata>(),
+                )?;
+                std::ptr::write_unaligned(ptr as *mut AVMasteringDisplayMetadata, value.into());
+            }
+
+            Ok(())
+        }
+
+        /// Attaches content light level information, replacing any existing entry.
+        pub fn set_content_light_level(&mut self, value: ContentLightLevel) -> Result<(), Error> {
+            unsafe {
+                let ptr = self.new_coded_side_data(
+                    packet::side_data::Type::ContentLightLevel,
+                    std::mem::size_of::<AVContentLightMetadata>(),
+                )?;
+                std::ptr::write_unaligned(ptr as *mut AVContentLightMetadata, value.into());
+            }
+
+            Ok(())
+        }
+    }
+}
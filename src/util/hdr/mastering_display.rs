@@ -0,0 +1,131 @@
+use std::mem;
+
+use crate::ffi::*;
+use crate::Rational;
+
+/// CIE 1931 xy chromaticity coordinates.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Chromaticity {
+    pub x: Rational,
+    pub y: Rational,
+}
+
+impl From<[AVRational; 2]> for Chromaticity {
+    fn from(value: [AVRational; 2]) -> Self {
+        Chromaticity {
+            x: value[0].into(),
+            y: value[1].into(),
+        }
+    }
+}
+
+impl From<Chromaticity> for [AVRational; 2] {
+    fn from(value: Chromaticity) -> Self {
+        [value.x.into(), value.y.into()]
+    }
+}
+
+/// Color primaries and white point of the mastering display.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct DisplayPrimaries {
+    pub red: Chromaticity,
+    pub green: Chromaticity,
+    pub blue: Chromaticity,
+    pub white_point: Chromaticity,
+}
+
+/// Luminance range of the mastering display, in cd/m².
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Luminance {
+    pub min: Rational,
+    pub max: Rational,
+}
+
+/// Mastering display color volume (SMPTE ST 2086), as found in HDR10 streams.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct MasteringDisplayMetadata {
+    pub primaries: Option<DisplayPrimaries>,
+    pub luminance: Option<Luminance>,
+}
+
+impl From<AVMasteringDisplayMetadata> for MasteringDisplayMetadata {
+    fn from(value: AVMasteringDisplayMetadata) -> Self {
+        let primaries = (value.has_primaries != 0).then(|| DisplayPrimaries {
+            red: value.display_primaries[0].into(),
+            green: value.display_primaries[1].into(),
+            blue: value.display_primaries[2].into(),
+            white_point: value.white_point.into(),
+        });
+
+        let luminance = (value.has_luminance != 0).then(|| Luminance {
+            min: value.min_luminance.into(),
+            max: value.max_luminance.into(),
+        });
+
+        MasteringDisplayMetadata {
+            primaries,
+            luminance,
+        }
+    }
+}
+
+impl From<MasteringDisplayMetadata> for AVMasteringDisplayMetadata {
+    fn from(value: MasteringDisplayMetadata) -> Self {
+        let mut raw: AVMasteringDisplayMetadata = unsafe { mem::zeroed() };
+
+        if let Some(primaries) = value.primaries {
+            raw.display_primaries = [
+                primaries.red.into(),
+                primaries.green.into(),
+                primaries.blue.into(),
+            ];
+            raw.white_point = primaries.white_point.into();
+            raw.has_primaries = 1;
+        }
+
+        if let Some(luminance) = value.luminance {
+            raw.min_luminance = luminance.min.into();
+            raw.max_luminance = luminance.max.into();
+            raw.has_luminance = 1;
+        }
+
+        raw
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let xy = |x, y| Chromaticity {
+            x: Rational(x, 50000),
+            y: Rational(y, 50000),
+        };
+
+        // BT.2020 primaries, D65 white point, 0.0001-1000 cd/m²
+        let metadata = MasteringDisplayMetadata {
+            primaries: Some(DisplayPrimaries {
+                red: xy(35400, 14600),
+                green: xy(8500, 39850),
+                blue: xy(6550, 2300),
+                white_point: xy(15635, 16450),
+            }),
+            luminance: Some(Luminance {
+                min: Rational(1, 10000),
+                max: Rational(1000, 1),
+            }),
+        };
+
+        let raw = AVMasteringDisplayMetadata::from(metadata);
+        assert_eq!(raw.has_primaries, 1);
+        assert_eq!(raw.has_luminance, 1);
+        assert_eq!(raw.display_primaries[1][0].num, 8500);
+        assert_eq!(MasteringDisplayMetadata::from(raw), metadata);
+
+        let empty = AVMasteringDisplayMetadata::from(MasteringDisplayMetadata::default());
+        assert_eq!(empty.has_primaries, 0);
+        assert_eq!(empty.has_luminance, 0);
+    }
+}
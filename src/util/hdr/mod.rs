@@ -0,0 +1,14 @@
+//! Typed HDR metadata carried as frame, packet and stream side data.
+
+pub mod mastering_display;
+pub use self::mastering_display::{
+    Chromaticity, DisplayPrimaries, Luminance, MasteringDisplayMetadata,
+};
+
+pub mod content_light;
+pub use self::content_light::ContentLightLevel;
+
+pub mod dynamic_plus;
+pub use self::dynamic_plus::{DynamicHdrPlus, OverlapProcess, Window};
+
+mod extensions;
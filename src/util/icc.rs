@@ -0,0 +1,199 @@
+//! ICC color profiles carried as frame, codec parameters and stream side data.
+//!
+//! The PNG, JPEG and TIFF encoders embed the profile of each frame, while
+//! muxers such as MOV and MP4 write the stream level profile.
+
+use std::ptr;
+use std::slice;
+
+use crate::frame::{self, side_data::Type};
+use crate::Error;
+
+#[cfg(all(feature = "codec", feature = "ffmpeg_6_1"))]
+use crate::codec::{packet, Parameters, ParametersMut, ParametersRef};
+#[cfg(all(feature = "format", not(feature = "ffmpeg_6_1")))]
+use crate::ffi::*;
+#[cfg(feature = "format")]
+use crate::format::stream::{Stream, StreamMut};
+#[cfg(all(feature = "codec", feature = "ffmpeg_6_1"))]
+use crate::macros::impl_for_many;
+
+impl frame::Video {
+    pub fn icc_profile(&self) -> Option<&[u8]> {
+        let side_data = self.side_data(Type::IccProfile)?;
+
+        // borrow the buffer for the lifetime of the frame rather than the side data handle
+        unsafe {
+            let ptr = side_data.as_ptr();
+            Some(slice::from_raw_parts((*ptr).data, (*ptr).size as usize))
+        }
+    }
+
+    /// Attaches an ICC profile, replacing any existing entry.
+    pub fn set_icc_profile(&mut self, value: &[u8]) -> Result<(), Error> {
+        self.remove_side_data(Type::IccProfile);
+
+        let mut side_data =
+            self.new_side_data(Type::IccProfile, value.len())
+                .ok_or(Error::Other {
+                    errno: libc::ENOMEM,
+                })?;
+
+        unsafe {
+            ptr::copy_nonoverlapping(value.as_ptr(), (*side_data.as_mut_ptr()).data, value.len());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(all(feature = "codec", feature = "ffmpeg_6_1"))]
+impl_for_many! {
+    impl for Parameters, ParametersRef<'p>, ParametersMut<'p> {
+        pub fn icc_profile(&self) -> Option<&[u8]> {
+            let side_data = self.coded_side_data(packet::side_data::Type::ICC_PROFILE)?;
+
+            unsafe {
+                let ptr = side_data.as_ptr();
+                Some(slice::from_raw_parts((*ptr).data, (*ptr).size))
+            }
+        }
+    }
+}
+
+#[cfg(all(feature = "codec", feature = "ffmpeg_6_1"))]
+impl_for_many! {
+    impl for Parameters, ParametersMut<'p> {
+        /// Attaches an ICC profile, replacing any existing entry.
+        pub fn set_icc_profile(&mut self, value: &[u8]) -> Result<(), Error> {
+            self.add_coded_side_data(packet::side_data::Type::ICC_PROFILE, value)
+        }
+    }
+}
+
+#[cfg(feature = "format")]
+impl<'a> Stream<'a> {
+    #[cfg(feature = "ffmpeg_6_1")]
+    pub fn icc_profile(&self) -> Option<&[u8]> {
+        let side_data = self
+            .parameters()
+            .coded_side_data(packet::side_data::Type::ICC_PROFILE)?;
+
+        unsafe {
+            let ptr = side_data.as_ptr();
+            Some(slice::from_raw_parts((*ptr).data, (*ptr).size))
+        }
+    }
+
+    #[cfg(not(feature = "ffmpeg_6_1"))]
+    pub fn icc_profile(&self) -> Option<&[u8]> {
+        unsafe {
+            let mut size = 0;
+            let ptr = av_stream_get_side_data(
+                self.as_ptr(),
+                AVPacketSideDataType::ICC_PROFILE,
+                &mut size,
+            );
+
+            if ptr.is_null() {
+                return None;
+            }
+
+            Some(slice::from_raw_parts(ptr, size as usize))
+        }
+    }
+}
+
+#[cfg(feature = "format")]
+impl<'a> StreamMut<'a> {
+    /// Attaches an ICC profile, replacing any existing entry.
+    #[cfg(feature = "ffmpeg_6_1")]
+    pub fn set_icc_profile(&mut self, value: &[u8]) -> Result<(), Error> {
+        self.parameters_mut().set_icc_profile(value)
+    }
+
+    /// Attaches an ICC profile, replacing any existing entry.
+    #[cfg(not(feature = "ffmpeg_6_1"))]
+    pub fn set_icc_profile(&mut self, value: &[u8]) -> Result<(), Error> {
+        unsafe {
+            let ptr = av_stream_new_side_data(
+                self.as_mut_ptr(),
+                AVPacketSideDataType::ICC_PROFILE,
+                value.len(),
+            );
+
+            if ptr.is_null() {
+                return Err(Error::Other {
+                    errno: libc::ENOMEM,
+                });
+            }
+
+            ptr::copy_nonoverlapping(value.as_ptr(), ptr, value.len());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(all(feature = "codec", feature = "ffmpeg_7_0"))]
+impl crate::codec::encoder::video::Video {
+    /// Sets the ICC profile the encoder exports as stream side data, replacing
+    /// any existing entry. Must be called before opening the encoder.
+    pub fn set_icc_profile(&mut self, value: &[u8]) -> Result<(), Error> {
+        unsafe {
+            let ptr = self.new_decoded_side_data(Type::IccProfile, value.len())?;
+            ptr::copy_nonoverlapping(value.as_ptr(), ptr, value.len());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::Pixel;
+
+    #[test]
+    fn frame() {
+        let mut frame = frame::Video::new(Pixel::RGB24, 16, 16);
+        assert_eq!(frame.icc_profile(), None);
+
+        frame.set_icc_profile(b"first profile").unwrap();
+        frame.set_icc_profile(b"second").unwrap();
+        assert_eq!(frame.icc_profile(), Some(&b"second"[..]));
+    }
+
+    #[cfg(all(feature = "codec", feature = "ffmpeg_6_1"))]
+    #[test]
+    fn parameters() {
+        let mut parameters = Parameters::new();
+        assert_eq!(parameters.icc_profile(), None);
+
+        parameters.set_icc_profile(b"profile").unwrap();
+        assert_eq!(parameters.icc_profile(), Some(&b"profile"[..]));
+    }
+
+    #[cfg(all(feature = "codec", feature = "ffmpeg_7_0"))]
+    #[test]
+    fn encoder() {
+        use crate::codec::{encoder, Context, Id};
+
+        let codec = encoder::find(Id::PNG).expect("can find PNG encoder");
+        let mut encoder = Context::new_with_codec(codec).encoder().video().unwrap();
+        encoder.set_icc_profile(b"first profile").unwrap();
+        encoder.set_icc_profile(b"profile").unwrap();
+
+        unsafe {
+            let ctx = encoder.as_ptr();
+            assert_eq!((*ctx).nb_decoded_side_data, 1);
+
+            let side_data = *(*ctx).decoded_side_data;
+            assert_eq!((*side_data).type_, Type::IccProfile.into());
+            assert_eq!(
+                slice::from_raw_parts((*side_data).data, (*side_data).size),
+                b"profile"
+            );
+        }
+    }
+}
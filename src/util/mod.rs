@@ -1,18 +1,33 @@
 pub mod channel_layout;
 pub mod chroma;
 pub mod color;
+pub mod detection_bbox;
 pub mod dictionary;
+pub mod display;
+pub mod dovi;
 pub mod error;
+pub mod exif;
+pub mod film_grain;
 pub mod format;
 pub mod frame;
+pub mod hdr;
+pub mod icc;
 pub mod interrupt;
 pub mod log;
 pub mod mathematics;
 pub mod media;
+pub mod motion_vector;
 pub mod option;
 pub mod picture;
 pub mod rational;
+pub mod region_of_interest;
+pub mod sei;
+pub mod spherical;
+pub mod stereo3d;
 pub mod time;
+pub mod timecode;
+pub mod user_data;
+pub mod video_enc_params;
 
 use crate::ffi::*;
 use crate::utils;
@@ -0,0 +1,178 @@
+//! Motion vectors exported by video decoders.
+//!
+//! Decoders only attach `MotionVectors` side data when asked to, see
+//! `decoder::ExportSideData::MOTION_VECTORS`.
+
+use std::iter::FusedIterator;
+use std::mem;
+use std::slice;
+
+use crate::ffi::*;
+use crate::frame::{side_data::Type, Frame};
+use crate::utils;
+
+#[derive(Copy, Clone)]
+pub struct MotionVector(AVMotionVector);
+
+impl MotionVector {
+    /// Where the block is predicted from: negative for a past reference frame,
+    /// positive for a future one.
+    pub fn source(&self) -> i32 {
+        self.0.source
+    }
+
+    /// Width and height of the block.
+    pub fn size(&self) -> (u8, u8) {
+        (self.0.w, self.0.h)
+    }
+
+    /// Absolute position of the block center in the source frame; may lie
+    /// outside the frame.
+    pub fn source_position(&self) -> (i16, i16) {
+        (self.0.src_x, self.0.src_y)
+    }
+
+    /// Absolute position of the block center in the current frame; may lie
+    /// outside the frame.
+    pub fn destination_position(&self) -> (i16, i16) {
+        (self.0.dst_x, self.0.dst_y)
+    }
+
+    /// Motion in units of `1 / motion_scale` pixels, such that
+    /// `source = destination + motion / motion_scale`.
+    pub fn motion(&self) -> (i32, i32) {
+        (self.0.motion_x, self.0.motion_y)
+    }
+
+    pub fn motion_scale(&self) -> u16 {
+        self.0.motion_scale
+    }
+
+    /// Reserved by FFmpeg, currently always 0.
+    pub fn flags(&self) -> u64 {
+        self.0.flags
+    }
+}
+
+impl From<AVMotionVector> for MotionVector {
+    fn from(value: AVMotionVector) -> Self {
+        MotionVector(value)
+    }
+}
+
+impl From<MotionVector> for AVMotionVector {
+    fn from(value: MotionVector) -> Self {
+        value.0
+    }
+}
+
+/// Iterator over the motion vectors of a frame.
+pub struct MotionVectors<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> MotionVectors<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        MotionVectors { data }
+    }
+}
+
+impl<'a> Iterator for MotionVectors<'a> {
+    type Item = MotionVector;
+
+    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
+        let value = unsafe { utils::read_struct::<AVMotionVector>(self.data)? };
+        self.data = &self.data[mem::size_of::<AVMotionVector>()..];
+
+        Some(MotionVector(value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let length = self.data.len() / mem::size_of::<AVMotionVector>();
+
+        (length, Some(length))
+    }
+}
+
+impl<'a> ExactSizeIterator for MotionVectors<'a> {}
+impl<'a> FusedIterator for MotionVectors<'a> {}
+
+impl Frame {
+    /// Motion vectors exported by the decoder; empty if the frame has none.
+    pub fn motion_vectors(&self) -> MotionVectors<'_> {
+        match self.side_data(Type::MotionVectors) {
+            // borrow the buffer for the lifetime of the frame rather than the side data handle
+            Some(side_data) => unsafe {
+                let ptr = side_data.as_ptr();
+                MotionVectors::new(slice::from_raw_parts((*ptr).data, (*ptr).size as usize))
+            },
+            None => MotionVectors::new(&[]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ptr;
+
+    use super::*;
+    use crate::format::Pixel;
+    use crate::frame;
+
+    fn vector(source: i32, dst_x: i16, motion_x: i32) -> AVMotionVector {
+        let mut raw: AVMotionVector = unsafe { mem::zeroed() };
+        raw.source = source;
+        raw.w = 16;
+        raw.h = 8;
+        raw.src_x = dst_x + (motion_x / 4) as i16;
+        raw.src_y = 4;
+        raw.dst_x = dst_x;
+        raw.dst_y = 4;
+        raw.motion_x = motion_x;
+        raw.motion_scale = 4;
+        raw
+    }
+
+    #[test]
+    fn iterator() {
+        let mut frame = frame::Video::new(Pixel::YUV420P, 32, 16);
+        assert_eq!(frame.motion_vectors().len(), 0);
+
+        let vectors = [vector(-1, 8, 12), vector(1, 24, -8)];
+        let size = mem::size_of::<AVMotionVector>();
+
+        // a trailing partial entry is ignored
+        let mut side_data = frame
+            .new_side_data(Type::MotionVectors, vectors.len() * size + 3)
+            .unwrap();
+
+        unsafe {
+            let data = (*side_data.as_mut_ptr()).data;
+
+            for (i, &raw) in vectors.iter().enumerate() {
+                ptr::write_unaligned(data.add(i * size) as *mut AVMotionVector, raw);
+            }
+        }
+
+        let mut iter = frame.motion_vectors();
+        assert_eq!(iter.len(), 2);
+
+        let first = iter.next().unwrap();
+        assert_eq!(first.source(), -1);
+        assert_eq!(first.size(), (16, 8));
+        assert_eq!(first.source_position(), (11, 4));
+        assert_eq!(first.destination_position(), (8, 4));
+        assert_eq!(first.motion(), (12, 0));
+        assert_eq!(first.motion_scale(), 4);
+        assert_eq!(first.flags(), 0);
+        assert_eq!(iter.len(), 1);
+
+        let second = iter.next().unwrap();
+        assert_eq!(second.source(), 1);
+        assert_eq!(second.source_position(), (22, 4));
+        assert_eq!(second.motion(), (-8, 0));
+
+        assert!(iter.next().is_none());
+        assert!(iter.next().is_none());
+    }
+}
@@ -0,0 +1,140 @@
+use std::marker::PhantomData;
+
+use super::{Flags, Type};
+use crate::ffi::*;
+use crate::utils;
+use crate::Rational;
+
+/// Default value of an option, typed according to its [`Type`].
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Value<'a> {
+    Int(i64),
+    Double(f64),
+    String(Option<&'a str>),
+    Rational(Rational),
+}
+
+/// Description of an option declared by an `AVClass`.
+#[derive(Clone, Copy)]
+pub struct Descriptor<'a> {
+    ptr: *const AVOption,
+
+    _marker: PhantomData<&'a AVClass>,
+}
+
+impl<'a> Descriptor<'a> {
+    pub unsafe fn wrap(ptr: *const AVOption) -> Self {
+        Descriptor {
+            ptr,
+            _marker: PhantomData,
+        }
+    }
+
+    pub unsafe fn as_ptr(&self) -> *const AVOption {
+        self.ptr
+    }
+}
+
+impl<'a> Descriptor<'a> {
+    pub fn name(&self) -> &'a str {
+        unsafe { utils::str_from_c_ptr((*self.as_ptr()).name) }
+    }
+
+    pub fn help(&self) -> Option<&'a str> {
+        unsafe { utils::optional_str_from_c_ptr((*self.as_ptr()).help) }
+    }
+
+    pub fn kind(&self) -> Type {
+        unsafe { Type::from((*self.as_ptr()).type_) }
+    }
+
+    pub fn flags(&self) -> Flags {
+        unsafe { Flags::from_bits_truncate((*self.as_ptr()).flags) }
+    }
+
+    pub fn min(&self) -> f64 {
+        unsafe { (*self.as_ptr()).min }
+    }
+
+    pub fn max(&self) -> f64 {
+        unsafe { (*self.as_ptr()).max }
+    }
+
+    /// Groups named constants with the options accepting them: constants share
+    /// the unit of their option.
+    pub fn unit(&self) -> Option<&'a str> {
+        unsafe { utils::optional_str_from_c_ptr((*self.as_ptr()).unit) }
+    }
+
+    /// Returns the default value, or `None` for binary and array options,
+    /// which have none.
+    pub fn default_value(&self) -> Option<Value<'a>> {
+        unsafe {
+            let value = &(*self.as_ptr()).default_val;
+            let kind = self.kind();
+
+            #[cfg(feature = "ffmpeg_7_0")]
+            if kind.contains(Type::FLAG_ARRAY) {
+                return None;
+            }
+
+            if kind == Type::DOUBLE || kind == Type::FLOAT {
+                Some(Value::Double(value.dbl))
+            } else if kind == Type::RATIONAL {
+                Some(Value::Rational(value.q.into()))
+            } else if kind == Type::STRING
+                || kind == Type::COLOR
+                || kind == Type::IMAGE_SIZE
+                || kind == Type::VIDEO_RATE
+                || kind == Type::DICTIONARY
+                || kind == Type::CHLAYOUT
+            {
+                Some(Value::String(utils::optional_str_from_c_ptr(value.str_)))
+            } else if kind == Type::BINARY {
+                None
+            } else {
+                Some(Value::Int(value.i64_))
+            }
+        }
+    }
+}
+
+/// Iterator over the options declared by an `AVClass`, constants included.
+pub struct Descriptors<'a> {
+    ptr: *const AVOption,
+
+    _marker: PhantomData<&'a AVClass>,
+}
+
+impl<'a> Descriptors<'a> {
+    /// Iterates the options of `class`, which may be null.
+    pub unsafe fn new(class: *const AVClass) -> Self {
+        Descriptors {
+            ptr: if class.is_null() {
+                std::ptr::null()
+            } else {
+                (*class).option
+            },
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a> Iterator for Descriptors<'a> {
+    type Item = Descriptor<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        unsafe {
+            if self.ptr.is_null() || (*self.ptr).name.is_null() {
+                return None;
+            }
+
+            let descriptor = Descriptor::wrap(self.ptr);
+            self.ptr = self.ptr.add(1);
+
+            Some(descriptor)
+        }
+    }
+}
+
+impl<'a> std::iter::FusedIterator for Descriptors<'a> {}
@@ -0,0 +1,21 @@
+use crate::ffi::*;
+use libc::c_int;
+
+bitflags::bitflags! {
+    pub struct Flags: c_int {
+        const ENCODING_PARAM  = AV_OPT_FLAG_ENCODING_PARAM;
+        const DECODING_PARAM  = AV_OPT_FLAG_DECODING_PARAM;
+        const AUDIO_PARAM     = AV_OPT_FLAG_AUDIO_PARAM;
+        const VIDEO_PARAM     = AV_OPT_FLAG_VIDEO_PARAM;
+        const SUBTITLE_PARAM  = AV_OPT_FLAG_SUBTITLE_PARAM;
+        /// Exported by the object, not settable by the user.
+        const EXPORT          = AV_OPT_FLAG_EXPORT;
+        const READONLY        = AV_OPT_FLAG_READONLY;
+        const BSF_PARAM       = AV_OPT_FLAG_BSF_PARAM;
+        /// Can be changed at runtime, e.g. through filter commands.
+        const RUNTIME_PARAM   = AV_OPT_FLAG_RUNTIME_PARAM;
+        const FILTERING_PARAM = AV_OPT_FLAG_FILTERING_PARAM;
+        const DEPRECATED      = AV_OPT_FLAG_DEPRECATED;
+        const CHILD_CONSTS    = AV_OPT_FLAG_CHILD_CONSTS;
+    }
+}
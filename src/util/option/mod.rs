@@ -1,6 +1,12 @@
 mod traits;
 pub use self::traits::{Gettable, Iterable, Settable};
 
+pub mod flag;
+pub use self::flag::Flags;
+
+mod descriptor;
+pub use self::descriptor::{Descriptor, Descriptors, Value};
+
 use crate::ffi::*;
 use libc::c_uint;
 #[cfg(feature = "serialize")]
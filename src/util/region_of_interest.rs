@@ -0,0 +1,192 @@
+//! Regions of interest, which encoders such as libx264, libx265 and libaom use
+//! to adjust the quantizer of parts of a frame.
+
+use std::mem;
+use std::ptr;
+
+use crate::ffi::*;
+use crate::frame::{self, side_data::Type};
+use crate::{utils, Error, Rational};
+
+/// A rectangle of a frame, as pixel distances of its edges from the top left
+/// corner, covering `left..right` and `top..bottom`.
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub struct RegionOfInterest {
+    pub top: i32,
+    pub bottom: i32,
+    pub left: i32,
+    pub right: i32,
+
+    /// Quantizer offset in `[-1, 1]`: negative values improve quality, positive
+    /// values save bits and 0 keeps the encoder's choice.
+    pub qoffset: Rational,
+}
+
+impl RegionOfInterest {
+    pub fn new(x: i32, y: i32, width: i32, height: i32, qoffset: Rational) -> Self {
+        RegionOfInterest {
+            top: y,
+            bottom: y + height,
+            left: x,
+            right: x + width,
+            qoffset,
+        }
+    }
+}
+
+impl From<AVRegionOfInterest> for RegionOfInterest {
+    fn from(value: AVRegionOfInterest) -> Self {
+        RegionOfInterest {
+            top: value.top,
+            bottom: value.bottom,
+            left: value.left,
+            right: value.right,
+            qoffset: value.qoffset.into(),
+        }
+    }
+}
+
+impl From<RegionOfInterest> for AVRegionOfInterest {
+    fn from(value: RegionOfInterest) -> Self {
+        AVRegionOfInterest {
+            self_size: mem::size_of::<AVRegionOfInterest>() as u32,
+            top: value.top,
+            bottom: value.bottom,
+            left: value.left,
+            right: value.right,
+            qoffset: value.qoffset.into(),
+        }
+    }
+}
+
+impl frame::Video {
+    /// Regions of interest attached to the frame, in order of priority.
+    pub fn regions_of_interest(&self) -> Vec<RegionOfInterest> {
+        let Some(side_data) = self.side_data(Type::REGIONS_OF_INTEREST) else {
+            return Vec::new();
+        };
+        let data = side_data.data();
+
+        // all entries share the self_size of the first one
+        let Some(first) = (unsafe { utils::read_struct::<AVRegionOfInterest>(data) }) else {
+            return Vec::new();
+        };
+        let stride = first.self_size as usize;
+
+        if stride < mem::size_of::<AVRegionOfInterest>() {
+            return Vec::new();
+        }
+
+        (0..data.len() / stride)
+            .filter_map(|i| unsafe {
+                utils::read_struct::<AVRegionOfInterest>(&data[i * stride..])
+            })
+            .map(RegionOfInterest::from)
+            .collect()
+    }
+
+    /// Attaches regions of interest, replacing any existing ones. When regions
+    /// overlap, encoders use the first one listed. An empty list only removes
+    /// the existing entry.
+    ///
+    /// Fails with `Error::InvalidData` if a quantizer offset has a zero
+    /// denominator or lies outside `[-1, 1]`.
+    pub fn set_regions_of_interest(&mut self, regions: &[RegionOfInterest]) -> Result<(), Error> {
+        let valid = |qoffset: Rational| {
+            let (num, den) = (
+                i64::from(qoffset.numerator()),
+                i64::from(qoffset.denominator()),
+            );
+
+            den != 0 && num.abs() <= den.abs()
+        };
+
+        if !regions.iter().all(|region| valid(region.qoffset)) {
+            return Err(Error::InvalidData);
+        }
+
+        self.remove_side_data(Type::REGIONS_OF_INTEREST);
+
+        if regions.is_empty() {
+            return Ok(());
+        }
+
+        let mut side_data = self
+            .new_side_data(
+                Type::REGIONS_OF_INTEREST,
+                regions.len() * mem::size_of::<AVRegionOfInterest>(),
+            )
+            .ok_or(Error::Other {
+                errno: libc::ENOMEM,
+            })?;
+
+        unsafe {
+            let ptr = (*side_data.as_mut_ptr()).data as *mut AVRegionOfInterest;
+
+            for (i, &region) in regions.iter().enumerate() {
+                ptr::write_unaligned(ptr.add(i), region.into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::Pixel;
+
+    #[test]
+    fn round_trip() {
+        let regions = [
+            RegionOfInterest::new(0, 0, 16, 8, Rational(-1, 2)),
+            RegionOfInterest::new(8, 4, 8, 12, Rational(1, 1)),
+        ];
+        let mut frame = frame::Video::new(Pixel::YUV420P, 16, 16);
+        assert!(frame.regions_of_interest().is_empty());
+
+        frame.set_regions_of_interest(&regions).unwrap();
+        assert_eq!(frame.regions_of_interest(), regions);
+
+        for qoffset in [Rational(1, 0), Rational(3, 2), Rational(-2, 1)] {
+            let region = RegionOfInterest::new(0, 0, 4, 4, qoffset);
+            assert_eq!(
+                frame.set_regions_of_interest(&[region]),
+                Err(Error::InvalidData)
+            );
+        }
+        assert_eq!(frame.regions_of_interest(), regions);
+
+        frame.set_regions_of_interest(&[]).unwrap();
+        assert!(frame.regions_of_interest().is_empty());
+    }
+
+    #[test]
+    fn stride() {
+        // entries written by a newer FFmpeg with a larger AVRegionOfInterest
+        let size = mem::size_of::<AVRegionOfInterest>();
+        let stride = size + 8;
+        let regions = [
+            RegionOfInterest::new(0, 0, 16, 16, Rational(-1, 10)),
+            RegionOfInterest::new(4, 4, 4, 4, Rational(1, 5)),
+        ];
+
+        let mut frame = frame::Video::new(Pixel::YUV420P, 16, 16);
+        let mut side_data = frame
+            .new_side_data(Type::REGIONS_OF_INTEREST, regions.len() * stride)
+            .unwrap();
+
+        unsafe {
+            let data = (*side_data.as_mut_ptr()).data;
+
+            for (i, &region) in regions.iter().enumerate() {
+                let mut raw = AVRegionOfInterest::from(region);
+                raw.self_size = stride as u32;
+                ptr::write_unaligned(data.add(i * stride) as *mut AVRegionOfInterest, raw);
+            }
+        }
+
+        assert_eq!(frame.regions_of_interest(), regions);
+    }
+}
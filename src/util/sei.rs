@@ -0,0 +1,115 @@
+//! User data unregistered SEI messages, as found in `SEI_UNREGISTERED` frame
+//! side data.
+//!
+//! The H.264 and H.265 decoders export every such message they encounter,
+//! while libx264 and the NVENC encoders insert the ones attached to frames
+//! (libx264 only with its `udu_sei` option enabled).
+
+use std::ptr;
+use std::slice;
+
+use crate::frame::{side_data::Type, Frame};
+use crate::Error;
+
+/// Size of the UUID prefixing the payload.
+pub const UUID_SIZE: usize = 16;
+
+/// A user data unregistered SEI message.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Unregistered {
+    pub uuid: [u8; UUID_SIZE],
+    pub payload: Vec<u8>,
+}
+
+impl Unregistered {
+    pub fn new(uuid: [u8; UUID_SIZE], payload: Vec<u8>) -> Self {
+        Unregistered { uuid, payload }
+    }
+
+    /// Splits raw side data into UUID and payload, returning `None` if it is
+    /// too short to hold a UUID.
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        if data.len() < UUID_SIZE {
+            return None;
+        }
+
+        let (uuid, payload) = data.split_at(UUID_SIZE);
+
+        Some(Unregistered {
+            uuid: uuid.try_into().ok()?,
+            payload: payload.to_vec(),
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(UUID_SIZE + self.payload.len());
+        data.extend_from_slice(&self.uuid);
+        data.extend_from_slice(&self.payload);
+
+        data
+    }
+}
+
+impl Frame {
+    /// Returns all unregistered SEI messages, in bitstream order.
+    pub fn sei_unregistered(&self) -> Vec<Unregistered> {
+        let kind = Type::SEI_UNREGISTERED.into();
+
+        unsafe {
+            let ptr = self.as_ptr();
+
+            if (*ptr).side_data.is_null() {
+                return Vec::new();
+            }
+
+            slice::from_raw_parts((*ptr).side_data, (*ptr).nb_side_data as usize)
+                .iter()
+                .filter(|side_data| (***side_data).type_ == kind)
+                .filter_map(|side_data| {
+                    let side_data = &**side_data;
+                    Unregistered::from_bytes(slice::from_raw_parts(
+                        side_data.data,
+                        side_data.size as usize,
+                    ))
+                })
+                .collect()
+        }
+    }
+
+    /// Attaches an unregistered SEI message after any existing ones. Use
+    /// `remove_side_data(Type::SEI_UNREGISTERED)` to drop them.
+    pub fn add_sei_unregistered(&mut self, value: &Unregistered) -> Result<(), Error> {
+        let data = value.to_bytes();
+
+        let mut side_data = self
+            .new_side_data(Type::SEI_UNREGISTERED, data.len())
+            .ok_or(Error::Other {
+                errno: libc::ENOMEM,
+            })?;
+
+        unsafe {
+            ptr::copy_nonoverlapping(data.as_ptr(), (*side_data.as_mut_ptr()).data, data.len());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytes() {
+        let message = Unregistered::new([7; UUID_SIZE], b"latency".to_vec());
+        let data = message.to_bytes();
+
+        assert_eq!(data.len(), UUID_SIZE + 7);
+        assert_eq!(Unregistered::from_bytes(&data), Some(message));
+
+        let empty = Unregistered::from_bytes(&[1; UUID_SIZE]).unwrap();
+        assert!(empty.payload.is_empty());
+
+        assert_eq!(Unregistered::from_bytes(&[0; UUID_SIZE - 1]), None);
+    }
+}
@@ -0,0 +1,300 @@
+//! Spherical (360°) video projection.
+
+use std::mem;
+
+use crate::ffi::*;
+use crate::frame::{self, side_data};
+use crate::{utils, Error};
+
+#[cfg(feature = "codec")]
+use crate::codec::packet;
+#[cfg(all(feature = "codec", feature = "ffmpeg_6_1"))]
+use crate::codec::{Parameters, ParametersMut, ParametersRef};
+#[cfg(feature = "format")]
+use crate::format::stream::{Stream, StreamMut};
+#[cfg(all(feature = "codec", feature = "ffmpeg_6_1"))]
+use crate::macros::impl_for_many;
+
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum Projection {
+    Equirectangular,
+    Cubemap,
+    /// Equirectangular with tiles cropped by [`Spherical::bounds`].
+    EquirectangularTile,
+    #[cfg(feature = "ffmpeg_7_1")]
+    HalfEquirectangular,
+    #[cfg(feature = "ffmpeg_7_1")]
+    Rectilinear,
+    #[cfg(feature = "ffmpeg_7_1")]
+    Fisheye,
+    #[cfg(feature = "ffmpeg_8_0")]
+    ParametricImmersive,
+}
+
+impl From<AVSphericalProjection> for Projection {
+    fn from(value: AVSphericalProjection) -> Self {
+        use AVSphericalProjection as AV;
+
+        match value {
+            AV::EQUIRECTANGULAR => Projection::Equirectangular,
+            AV::CUBEMAP => Projection::Cubemap,
+            AV::EQUIRECTANGULAR_TILE => Projection::EquirectangularTile,
+            #[cfg(feature = "ffmpeg_7_1")]
+            AV::HALF_EQUIRECTANGULAR => Projection::HalfEquirectangular,
+            #[cfg(feature = "ffmpeg_7_1")]
+            AV::RECTILINEAR => Projection::Rectilinear,
+            #[cfg(feature = "ffmpeg_7_1")]
+            AV::FISHEYE => Projection::Fisheye,
+            #[cfg(feature = "ffmpeg_8_0")]
+            AV::PARAMETRIC_IMMERSIVE => Projection::ParametricImmersive,
+
+            _ => unimplemented!(),
+        }
+    }
+}
+
+impl From<Projection> for AVSphericalProjection {
+    fn from(value: Projection) -> Self {
+        use AVSphericalProjection as AV;
+
+        match value {
+            Projection::Equirectangular => AV::EQUIRECTANGULAR,
+            Projection::Cubemap => AV::CUBEMAP,
+            Projection::EquirectangularTile => AV::EQUIRECTANGULAR_TILE,
+            #[cfg(feature = "ffmpeg_7_1")]
+            Projection::HalfEquirectangular => AV::HALF_EQUIRECTANGULAR,
+            #[cfg(feature = "ffmpeg_7_1")]
+            Projection::Rectilinear => AV::RECTILINEAR,
+            #[cfg(feature = "ffmpeg_7_1")]
+            Projection::Fisheye => AV::FISHEYE,
+            #[cfg(feature = "ffmpeg_8_0")]
+            Projection::ParametricImmersive => AV::PARAMETRIC_IMMERSIVE,
+        }
+    }
+}
+
+/// Distances of the edges of the visible tile from the edges of the full
+/// projection, as 0.32 fixed point fractions of its size.
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Default)]
+pub struct Bounds {
+    pub left: u32,
+    pub top: u32,
+    pub right: u32,
+    pub bottom: u32,
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct Spherical {
+    pub projection: Projection,
+
+    /// Rotation around the up vector, in degrees.
+    pub yaw: f64,
+    /// Rotation around the right vector, in degrees.
+    pub pitch: f64,
+    /// Rotation around the forward vector, in degrees.
+    pub roll: f64,
+
+    /// Only meaningful for [`Projection::EquirectangularTile`].
+    pub bounds: Bounds,
+    /// Pixels of padding around each cube face, only meaningful for
+    /// [`Projection::Cubemap`].
+    pub padding: u32,
+}
+
+impl Spherical {
+    /// Creates an unrotated mapping with the given projection.
+    pub fn new(projection: Projection) -> Self {
+        Spherical {
+            projection,
+            yaw: 0.0,
+            pitch: 0.0,
+            roll: 0.0,
+            bounds: Bounds::default(),
+            padding: 0,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        unsafe { utils::str_from_c_ptr(av_spherical_projection_name(self.projection.into())) }
+    }
+}
+
+/// Converts 16.16 fixed point to floating point.
+fn from_fixed(value: i32) -> f64 {
+    f64::from(value) / 65536.0
+}
+
+/// Converts floating point to 16.16 fixed point.
+fn to_fixed(value: f64) -> i32 {
+    (value * 65536.0).round() as i32
+}
+
+impl From<AVSphericalMapping> for Spherical {
+    fn from(value: AVSphericalMapping) -> Self {
+        Spherical {
+            projection: value.projection.into(),
+            yaw: from_fixed(value.yaw),
+            pitch: from_fixed(value.pitch),
+            roll: from_fixed(value.roll),
+            bounds: Bounds {
+                left: value.bound_left,
+                top: value.bound_top,
+                right: value.bound_right,
+                bottom: value.bound_bottom,
+            },
+            padding: value.padding,
+        }
+    }
+}
+
+impl From<Spherical> for AVSphericalMapping {
+    fn from(value: Spherical) -> Self {
+        let mut raw: AVSphericalMapping = unsafe { mem::zeroed() };
+
+        raw.projection = value.projection.into();
+        raw.yaw = to_fixed(value.yaw);
+        raw.pitch = to_fixed(value.pitch);
+        raw.roll = to_fixed(value.roll);
+        raw.bound_left = value.bounds.left;
+        raw.bound_top = value.bounds.top;
+        raw.bound_right = value.bounds.right;
+        raw.bound_bottom = value.bounds.bottom;
+        raw.padding = value.padding;
+
+        raw
+    }
+}
+
+impl frame::Video {
+    pub fn spherical(&self) -> Option<Spherical> {
+        let side_data = self.side_data(side_data::Type::Spherical)?;
+
+        unsafe { utils::read_struct::<AVSphericalMapping>(side_data.data()) }.map(Spherical::from)
+    }
+
+    /// Attaches a spherical mapping, replacing any existing entry.
+    pub fn set_spherical(&mut self, value: Spherical) -> Result<(), Error> {
+        self.remove_side_data(side_data::Type::Spherical);
+
+        let mut side_data = self
+            .new_side_data(
+                side_data::Type::Spherical,
+                mem::size_of::<AVSphericalMapping>(),
+            )
+            .ok_or(Error::Other {
+                errno: libc::ENOMEM,
+            })?;
+
+        unsafe {
+            std::ptr::write_unaligned(
+                (*side_data.as_mut_ptr()).data as *mut AVSphericalMapping,
+                value.into(),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "codec")]
+impl<'a> packet::SideData<'a> {
+    /// Returns the spherical mapping if this is a `DataSpherical` entry.
+    pub fn spherical(&self) -> Option<Spherical> {
+        unsafe { self.payload::<AVSphericalMapping>(packet::side_data::Type::DataSpherical) }
+            .map(Spherical::from)
+    }
+}
+
+#[cfg(all(feature = "codec", feature = "ffmpeg_6_1"))]
+impl_for_many! {
+    impl for Parameters, ParametersRef<'p>, ParametersMut<'p> {
+        pub fn spherical(&self) -> Option<Spherical> {
+            self.coded_side_data(packet::side_data::Type::DataSpherical)?.spherical()
+        }
+    }
+}
+
+#[cfg(all(feature = "codec", feature = "ffmpeg_6_1"))]
+impl_for_many! {
+    impl for Parameters, ParametersMut<'p> {
+        /// Attaches a spherical mapping, replacing any existing entry.
+        pub fn set_spherical(&mut self, value: Spherical) -> Result<(), Error> {
+            unsafe {
+                let ptr = self.new_coded_side_data(
+                    packet::side_data::Type::DataSpherical,
+                    mem::size_of::<AVSphericalMapping>(),
+                )?;
+                std::ptr::write_unaligned(ptr as *mut AVSphericalMapping, value.into());
+            }
+
+            Ok(())
+        }
+    }
+}
+
+#[cfg(feature = "format")]
+impl<'a> Stream<'a> {
+    #[cfg(feature = "ffmpeg_6_1")]
+    pub fn spherical(&self) -> Option<Spherical> {
+        self.parameters().spherical()
+    }
+
+    #[cfg(not(feature = "ffmpeg_6_1"))]
+    pub fn spherical(&self) -> Option<Spherical> {
+        unsafe {
+            let mut size = 0;
+            let ptr =
+                av_stream_get_side_data(self.as_ptr(), AVPacketSideDataType::SPHERICAL, &mut size);
+
+            if ptr.is_null() {
+                return None;
+            }
+
+            utils::read_struct::<AVSphericalMapping>(std::slice::from_raw_parts(ptr, size as usize))
+                .map(Spherical::from)
+        }
+    }
+}
+
+#[cfg(feature = "format")]
+impl<'a> StreamMut<'a> {
+    /// Attaches a spherical mapping, replacing any existing entry.
+    #[cfg(feature = "ffmpeg_6_1")]
+    pub fn set_spherical(&mut self, value: Spherical) -> Result<(), Error> {
+        self.parameters_mut().set_spherical(value)
+    }
+
+    /// Attaches a spherical mapping, replacing any existing entry.
+    #[cfg(not(feature = "ffmpeg_6_1"))]
+    pub fn set_spherical(&mut self, value: Spherical) -> Result<(), Error> {
+        unsafe {
+            let ptr = av_stream_new_side_data(
+                self.as_mut_ptr(),
+                AVPacketSideDataType::SPHERICAL,
+                mem::size_of::<AVSphericalMapping>(),
+            );
+
+            if ptr.is_null() {
+                return Err(Error::Other {
+                    errno: libc::ENOMEM,
+                });
+            }
+
+            std::ptr::write_unaligned(ptr as *mut AVSphericalMapping, value.into());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_point() {
+        assert_eq!(to_fixed(90.0), 90 << 16);
+        assert_eq!(to_fixed(-45.5), -(91 << 15));
+        assert_eq!(from_fixed(to_fixed(12.25)), 12.25);
+    }
+}
@@ -0,0 +1,413 @@
+//! Stereoscopic 3D packing of video frames.
+
+use std::mem;
+
+use crate::ffi::*;
+use crate::frame::{self, side_data};
+use crate::Error;
+#[cfg(feature = "ffmpeg_7_1")]
+use crate::Rational;
+
+#[cfg(feature = "codec")]
+use crate::codec::packet;
+#[cfg(all(feature = "codec", feature = "ffmpeg_6_1"))]
+use crate::codec::{Parameters, ParametersMut, ParametersRef};
+#[cfg(feature = "format")]
+use crate::format::stream::{Stream, StreamMut};
+#[cfg(all(feature = "codec", feature = "ffmpeg_6_1"))]
+use crate::macros::impl_for_many;
+
+/// How the views are packed within a frame.
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum Type {
+    /// Not stereoscopic.
+    _2D,
+    SideBySide,
+    TopBottom,
+    /// Views alternate frame by frame.
+    FrameSequence,
+    Checkerboard,
+    SideBySideQuincunx,
+    /// Views alternate line by line.
+    Lines,
+    /// Views alternate column by column.
+    Columns,
+    #[cfg(feature = "ffmpeg_7_1")]
+    Unspecified,
+}
+
+impl From<AVStereo3DType> for Type {
+    fn from(value: AVStereo3DType) -> Self {
+        use AVStereo3DType as AV;
+
+        match value {
+            AV::_2D => Type::_2D,
+            AV::SIDEBYSIDE => Type::SideBySide,
+            AV::TOPBOTTOM => Type::TopBottom,
+            AV::FRAMESEQUENCE => Type::FrameSequence,
+            AV::CHECKERBOARD => Type::Checkerboard,
+            AV::SIDEBYSIDE_QUINCUNX => Type::SideBySideQuincunx,
+            AV::LINES => Type::Lines,
+            AV::COLUMNS => Type::Columns,
+            #[cfg(feature = "ffmpeg_7_1")]
+            AV::UNSPEC => Type::Unspecified,
+
+            _ => unimplemented!(),
+        }
+    }
+}
+
+impl From<Type> for AVStereo3DType {
+    fn from(value: Type) -> Self {
+        use AVStereo3DType as AV;
+
+        match value {
+            Type::_2D => AV::_2D,
+            Type::SideBySide => AV::SIDEBYSIDE,
+            Type::TopBottom => AV::TOPBOTTOM,
+            Type::FrameSequence => AV::FRAMESEQUENCE,
+            Type::Checkerboard => AV::CHECKERBOARD,
+            Type::SideBySideQuincunx => AV::SIDEBYSIDE_QUINCUNX,
+            Type::Lines => AV::LINES,
+            Type::Columns => AV::COLUMNS,
+            #[cfg(feature = "ffmpeg_7_1")]
+            Type::Unspecified => AV::UNSPEC,
+        }
+    }
+}
+
+/// Which views a frame contains.
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum View {
+    /// Both views, packed according to the [`Type`].
+    Packed,
+    Left,
+    Right,
+    #[cfg(feature = "ffmpeg_7_1")]
+    Unspecified,
+}
+
+impl From<AVStereo3DView> for View {
+    fn from(value: AVStereo3DView) -> Self {
+        use AVStereo3DView as AV;
+
+        match value {
+            AV::PACKED => View::Packed,
+            AV::LEFT => View::Left,
+            AV::RIGHT => View::Right,
+            #[cfg(feature = "ffmpeg_7_1")]
+            AV::UNSPEC => View::Unspecified,
+
+            _ => unimplemented!(),
+        }
+    }
+}
+
+impl From<View> for AVStereo3DView {
+    fn from(value: View) -> Self {
+        use AVStereo3DView as AV;
+
+        match value {
+            View::Packed => AV::PACKED,
+            View::Left => AV::LEFT,
+            View::Right => AV::RIGHT,
+            #[cfg(feature = "ffmpeg_7_1")]
+            View::Unspecified => AV::UNSPEC,
+        }
+    }
+}
+
+#[cfg(feature = "ffmpeg_7_1")]
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum PrimaryEye {
+    None,
+    Left,
+    Right,
+}
+
+#[cfg(feature = "ffmpeg_7_1")]
+impl From<AVStereo3DPrimaryEye> for PrimaryEye {
+    fn from(value: AVStereo3DPrimaryEye) -> Self {
+        use AVStereo3DPrimaryEye as AV;
+
+        match value {
+            AV::NONE => PrimaryEye::None,
+            AV::LEFT => PrimaryEye::Left,
+            AV::RIGHT => PrimaryEye::Right,
+
+            _ => unimplemented!(),
+        }
+    }
+}
+
+#[cfg(feature = "ffmpeg_7_1")]
+impl From<PrimaryEye> for AVStereo3DPrimaryEye {
+    fn from(value: PrimaryEye) -> Self {
+        use AVStereo3DPrimaryEye as AV;
+
+        match value {
+            PrimaryEye::None => AV::NONE,
+            PrimaryEye::Left => AV::LEFT,
+            PrimaryEye::Right => AV::RIGHT,
+        }
+    }
+}
+
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub struct Stereo3D {
+    pub kind: Type,
+    /// Whether the right view comes first (left/top, or first in sequence).
+    pub inverted: bool,
+    pub view: View,
+
+    #[cfg(feature = "ffmpeg_7_1")]
+    pub primary_eye: PrimaryEye,
+    /// Distance between the camera centers, in micrometers; 0 if unknown.
+    #[cfg(feature = "ffmpeg_7_1")]
+    pub baseline: u32,
+    /// Relative shift of the left and right images in `[-1, 1]`.
+    #[cfg(feature = "ffmpeg_7_1")]
+    pub horizontal_disparity_adjustment: Rational,
+    /// Horizontal field of view, in degrees; 0 if unknown.
+    #[cfg(feature = "ffmpeg_7_1")]
+    pub horizontal_field_of_view: Rational,
+}
+
+impl Stereo3D {
+    /// Creates packed, non-inverted stereo of type `kind`.
+    pub fn new(kind: Type) -> Self {
+        Stereo3D {
+            kind,
+            inverted: false,
+            view: View::Packed,
+
+            #[cfg(feature = "ffmpeg_7_1")]
+            primary_eye: PrimaryEye::None,
+            #[cfg(feature = "ffmpeg_7_1")]
+            baseline: 0,
+            #[cfg(feature = "ffmpeg_7_1")]
+            horizontal_disparity_adjustment: Rational(0, 1),
+            #[cfg(feature = "ffmpeg_7_1")]
+            horizontal_field_of_view: Rational(0, 1),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        unsafe {
+            crate::utils::str_from_c_ptr(av_stereo3d_type_name(
+                AVStereo3DType::from(self.kind).0 as _,
+            ))
+        }
+    }
+}
+
+impl From<AVStereo3D> for Stereo3D {
+    fn from(value: AVStereo3D) -> Self {
+        Stereo3D {
+            kind: value.type_.into(),
+            inverted: value.flags & AV_STEREO3D_FLAG_INVERT != 0,
+            view: value.view.into(),
+
+            #[cfg(feature = "ffmpeg_7_1")]
+            primary_eye: value.primary_eye.into(),
+            #[cfg(feature = "ffmpeg_7_1")]
+            baseline: value.baseline,
+            #[cfg(feature = "ffmpeg_7_1")]
+            horizontal_disparity_adjustment: value.horizontal_disparity_adjustment.into(),
+            #[cfg(feature = "ffmpeg_7_1")]
+            horizontal_field_of_view: value.horizontal_field_of_view.into(),
+        }
+    }
+}
+
+impl From<Stereo3D> for AVStereo3D {
+    fn from(value: Stereo3D) -> Self {
+        let mut raw: AVStereo3D = unsafe { mem::zeroed() };
+
+        raw.type_ = value.kind.into();
+        raw.flags = if value.inverted {
+            AV_STEREO3D_FLAG_INVERT
+        } else {
+            0
+        };
+        raw.view = value.view.into();
+
+        #[cfg(feature = "ffmpeg_7_1")]
+        {
+            raw.primary_eye = value.primary_eye.into();
+            raw.baseline = value.baseline;
+            raw.horizontal_disparity_adjustment = value.horizontal_disparity_adjustment.into();
+            raw.horizontal_field_of_view = value.horizontal_field_of_view.into();
+        }
+
+        raw
+    }
+}
+
+impl frame::Video {
+    pub fn stereo3d(&self) -> Option<Stereo3D> {
+        let side_data = self.side_data(side_data::Type::Stereo3D)?;
+
+        unsafe { crate::utils::read_struct::<AVStereo3D>(side_data.data()) }.map(Stereo3D::from)
+    }
+
+    /// Attaches stereo 3D information, replacing any existing entry.
+    pub fn set_stereo3d(&mut self, value: Stereo3D) -> Result<(), Error> {
+        self.remove_side_data(side_data::Type::Stereo3D);
+
+        unsafe {
+            let ptr = av_stereo3d_create_side_data(self.as_mut_ptr());
+
+            if ptr.is_null() {
+                return Err(Error::Other {
+                    errno: libc::ENOMEM,
+                });
+            }
+
+            *ptr = value.into();
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "codec")]
+impl<'a> packet::SideData<'a> {
+    /// Returns the stereo 3D information if this is a `Stereo3d` entry.
+    pub fn stereo3d(&self) -> Option<Stereo3D> {
+        unsafe { self.payload::<AVStereo3D>(packet::side_data::Type::Stereo3d) }.map(Stereo3D::from)
+    }
+}
+
+#[cfg(all(feature = "codec", feature = "ffmpeg_6_1"))]
+impl_for_many! {
+    impl for Parameters, ParametersRef<'p>, ParametersMut<'p> {
+        pub fn stereo3d(&self) -> Option<Stereo3D> {
+            self.coded_side_data(packet::side_data::Type::Stereo3d)?.stereo3d()
+        }
+    }
+}
+
+#[cfg(all(feature = "codec", feature = "ffmpeg_6_1"))]
+impl_for_many! {
+    impl for Parameters, ParametersMut<'p> {
+        /// Attaches stereo 3D information, replacing any existing entry.
+        pub fn set_stereo3d(&mut self, value: Stereo3D) -> Result<(), Error> {
+            unsafe {
+                let ptr = self.new_coded_side_data(
+                    packet::side_data::Type::Stereo3d,
+                    mem::size_of::<AVStereo3D>(),
+                )?;
+                std::ptr::write_unaligned(ptr as *mut AVStereo3D, value.into());
+            }
+
+            Ok(())
+        }
+    }
+}
+
+#[cfg(feature = "format")]
+impl<'a> Stream<'a> {
+    #[cfg(feature = "ffmpeg_6_1")]
+    pub fn stereo3d(&self) -> Option<Stereo3D> {
+        self.parameters().stereo3d()
+    }
+
+    #[cfg(not(feature = "ffmpeg_6_1"))]
+    pub fn stereo3d(&self) -> Option<Stereo3D> {
+        unsafe {
+            let mut size = 0;
+            let ptr =
+                av_stream_get_side_data(self.as_ptr(), AVPacketSideDataType::STEREO3D, &mut size);
+
+            if ptr.is_null() {
+                return None;
+            }
+
+            crate::utils::read_struct::<AVStereo3D>(std::slice::from_raw_parts(ptr, size as usize))
+                .map(Stereo3D::from)
+        }
+    }
+}
+
+#[cfg(feature = "format")]
+impl<'a> StreamMut<'a> {
+    /// Attaches stereo 3D information, replacing any existing entry.
+    #[cfg(feature = "ffmpeg_6_1")]
+    pub fn set_stereo3d(&mut self, value: Stereo3D) -> Result<(), Error> {
+        self.parameters_mut().set_stereo3d(value)
+    }
+
+    /// Attaches stereo 3D information, replacing any existing entry.
+    #[cfg(not(feature = "ffmpeg_6_1"))]
+    pub fn set_stereo3d(&mut self, value: Stereo3D) -> Result<(), Error> {
+        unsafe {
+            let ptr = av_stream_new_side_data(
+                self.as_mut_ptr(),
+                AVPacketSideDataType::STEREO3D,
+                mem::size_of::<AVStereo3D>(),
+            );
+
+            if ptr.is_null() {
+                return Err(Error::Other {
+                    errno: libc::ENOMEM,
+                });
+            }
+
+            std::ptr::write_unaligned(ptr as *mut AVStereo3D, value.into());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::Pixel;
+
+    fn stereo() -> Stereo3D {
+        Stereo3D {
+            inverted: true,
+            view: View::Right,
+
+            #[cfg(feature = "ffmpeg_7_1")]
+            primary_eye: PrimaryEye::Left,
+            #[cfg(feature = "ffmpeg_7_1")]
+            baseline: 63_000,
+            #[cfg(feature = "ffmpeg_7_1")]
+            horizontal_disparity_adjustment: Rational(-1, 4),
+            #[cfg(feature = "ffmpeg_7_1")]
+            horizontal_field_of_view: Rational(90, 1),
+
+            ..Stereo3D::new(Type::TopBottom)
+        }
+    }
+
+    #[test]
+    fn frame() {
+        let mut frame = frame::Video::new(Pixel::YUV420P, 16, 16);
+        assert_eq!(frame.stereo3d(), None);
+
+        frame.set_stereo3d(stereo()).unwrap();
+        assert_eq!(frame.stereo3d(), Some(stereo()));
+
+        frame.set_stereo3d(Stereo3D::new(Type::Lines)).unwrap();
+        assert_eq!(frame.stereo3d(), Some(Stereo3D::new(Type::Lines)));
+        assert_eq!(frame.stereo3d().unwrap().name(), "interleaved lines");
+    }
+
+    #[cfg(feature = "format")]
+    #[test]
+    fn stream() {
+        let mut octx = crate::format::output_as("", "null").unwrap();
+        let mut stream = octx.add_stream(crate::codec::Id::RAWVIDEO).unwrap();
+        assert_eq!(stream.stereo3d(), None);
+
+        stream.set_stereo3d(stereo()).unwrap();
+        assert_eq!(stream.stereo3d(), Some(stereo()));
+
+        stream.set_stereo3d(Stereo3D::new(Type::Columns)).unwrap();
+        assert_eq!(stream.stereo3d(), Some(Stereo3D::new(Type::Columns)));
+    }
+}
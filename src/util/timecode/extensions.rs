@@ -0,0 +1,95 @@
+use std::mem;
+
+use super::{mpeg_to_string, Timecode};
+use crate::frame::{side_data::Type, Frame};
+use crate::{utils, Error, Rational};
+
+#[cfg(feature = "codec")]
+use crate::codec::packet;
+#[cfg(feature = "format")]
+use crate::format::stream::Stream;
+
+/// Decodes `S12M_TIMECODE` side data: a count followed by up to three binary
+/// SMPTE ST 12-1 timecodes.
+fn s12m(data: &[u8]) -> Vec<u32> {
+    let mut words = data
+        .chunks_exact(mem::size_of::<u32>())
+        .map(|word| u32::from_ne_bytes(word.try_into().unwrap()));
+
+    let count = words.next().unwrap_or(0).min(3) as usize;
+
+    words.take(count).collect()
+}
+
+impl Frame {
+    /// Binary SMPTE ST 12-1 timecodes attached to the frame, one per field or
+    /// frame of the access unit.
+    pub fn s12m_timecodes(&self) -> Vec<u32> {
+        self.side_data(Type::S12M_TIMECODE)
+            .map(|side_data| s12m(side_data.data()))
+            .unwrap_or_default()
+    }
+
+    /// Attaches up to three binary SMPTE ST 12-1 timecodes, replacing any existing entry.
+    pub fn set_s12m_timecodes(&mut self, value: &[u32]) -> Result<(), Error> {
+        let value = &value[..value.len().min(3)];
+
+        self.remove_side_data(Type::S12M_TIMECODE);
+
+        let mut side_data = self
+            .new_side_data(Type::S12M_TIMECODE, mem::size_of::<[u32; 4]>())
+            .ok_or(Error::Other {
+                errno: libc::ENOMEM,
+            })?;
+
+        let mut words = [0u32; 4];
+        words[0] = value.len() as u32;
+        words[1..=value.len()].copy_from_slice(value);
+
+        unsafe {
+            std::ptr::write_unaligned((*side_data.as_mut_ptr()).data as *mut [u32; 4], words);
+        }
+
+        Ok(())
+    }
+
+    /// The 25-bit MPEG GOP timecode attached to the frame.
+    pub fn gop_timecode(&self) -> Option<u32> {
+        let side_data = self.side_data(Type::GOPTimecode)?;
+
+        unsafe { utils::read_struct::<i64>(side_data.data()) }.map(|tc| tc as u32)
+    }
+
+    /// Returns the source timecode of the frame at `rate`, from its S12M
+    /// timecode or, failing that, from its GOP timecode.
+    pub fn timecode(&self, rate: Rational) -> Option<Timecode> {
+        if let Some(&smpte) = self.s12m_timecodes().first() {
+            return Timecode::from_smpte(rate, smpte).ok();
+        }
+
+        let tc = self.gop_timecode()?;
+        Timecode::parse(&mpeg_to_string(tc), rate).ok()
+    }
+}
+
+#[cfg(feature = "codec")]
+impl<'a> packet::SideData<'a> {
+    /// Returns the binary SMPTE ST 12-1 timecodes if this is an `S12M_TIMECODE` entry.
+    pub fn s12m_timecodes(&self) -> Option<Vec<u32>> {
+        (self.kind() == packet::side_data::Type::S12M_TIMECODE).then(|| s12m(self.data()))
+    }
+}
+
+#[cfg(feature = "format")]
+impl<'a> Stream<'a> {
+    /// Returns the start timecode from the `timecode` metadata, as set by the
+    /// MOV (`tmcd` track), MXF and other demuxers.
+    pub fn timecode(&self) -> Option<Timecode> {
+        let value = self.metadata().get("timecode")?.to_owned();
+        let rate = [self.avg_frame_rate(), self.rate()]
+            .into_iter()
+            .find(|rate| rate.numerator() > 0 && rate.denominator() > 0)?;
+
+        Timecode::parse(&value, rate).ok()
+    }
+}
@@ -0,0 +1,10 @@
+use crate::ffi::*;
+use libc::c_int;
+
+bitflags::bitflags! {
+    pub struct Flags: c_int {
+        const DROP_FRAME     = AVTimecodeFlag::DROPFRAME.0 as _;
+        const MAX_24_HOURS   = AVTimecodeFlag::_24HOURSMAX.0 as _;
+        const ALLOW_NEGATIVE = AVTimecodeFlag::ALLOWNEGATIVE.0 as _;
+    }
+}
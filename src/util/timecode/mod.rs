@@ -0,0 +1,253 @@
+//! SMPTE timecodes.
+//!
+//! A [`Timecode`] anchors a frame rate and a start frame, and converts frame
+//! numbers relative to that start into `hh:mm:ss:ff` strings and back. A `;`
+//! before the frame field denotes NTSC drop-frame counting.
+
+pub mod flag;
+pub use self::flag::Flags;
+
+mod extensions;
+
+use std::ffi::{CStr, CString};
+use std::fmt;
+use std::mem;
+use std::ptr;
+
+use libc::c_char;
+
+use crate::ffi::*;
+use crate::{Error, Rational};
+
+const STRING_SIZE: usize = AV_TIMECODE_STR_SIZE as usize;
+
+#[derive(Copy, Clone)]
+pub struct Timecode(AVTimecode);
+
+impl Timecode {
+    /// Creates a timecode starting at frame `start`.
+    pub fn new(rate: Rational, flags: Flags, start: i32) -> Result<Self, Error> {
+        unsafe {
+            let mut tc = mem::zeroed();
+
+            match av_timecode_init(&mut tc, rate.into(), flags.bits(), start, ptr::null_mut()) {
+                e if e < 0 => Err(Error::from(e)),
+                _ => Ok(Timecode(tc)),
+            }
+        }
+    }
+
+    /// Creates a timecode starting at `hours:minutes:seconds:frames`.
+    pub fn from_components(
+        rate: Rational,
+        flags: Flags,
+        hours: i32,
+        minutes: i32,
+        seconds: i32,
+        frames: i32,
+    ) -> Result<Self, Error> {
+        unsafe {
+            let mut tc = mem::zeroed();
+
+            match av_timecode_init_from_components(
+                &mut tc,
+                rate.into(),
+                flags.bits(),
+                hours,
+                minutes,
+                seconds,
+                frames,
+                ptr::null_mut(),
+            ) {
+                e if e < 0 => Err(Error::from(e)),
+                _ => Ok(Timecode(tc)),
+            }
+        }
+    }
+
+    /// Parses a timecode such as `01:00:00:00`. A `;` or `.` before the frame
+    /// field (`01:00:00;00`) selects drop-frame counting.
+    pub fn parse(value: &str, rate: Rational) -> Result<Self, Error> {
+        let value = CString::new(value).map_err(|_| Error::InvalidData)?;
+
+        unsafe {
+            let mut tc = mem::zeroed();
+
+            match av_timecode_init_from_string(
+                &mut tc,
+                rate.into(),
+                value.as_ptr(),
+                ptr::null_mut(),
+            ) {
+                e if e < 0 => Err(Error::from(e)),
+                _ => Ok(Timecode(tc)),
+            }
+        }
+    }
+
+    /// Creates a timecode starting at a binary SMPTE ST 12-1 timecode, as found
+    /// in `S12M_TIMECODE` side data.
+    pub fn from_smpte(rate: Rational, smpte: u32) -> Result<Self, Error> {
+        Self::parse(&smpte_to_string(rate, smpte, false), rate)
+    }
+
+    /// Returns whether FFmpeg supports timecodes at `rate`.
+    pub fn is_supported_rate(rate: Rational) -> bool {
+        unsafe { av_timecode_check_frame_rate(rate.into()) == 0 }
+    }
+
+    #[inline(always)]
+    pub fn as_ptr(&self) -> *const AVTimecode {
+        &self.0
+    }
+
+    #[inline(always)]
+    pub fn as_mut_ptr(&mut self) -> *mut AVTimecode {
+        &mut self.0
+    }
+}
+
+impl Timecode {
+    /// The frame number the timecode starts at.
+    pub fn start(&self) -> i32 {
+        self.0.start
+    }
+
+    pub fn rate(&self) -> Rational {
+        self.0.rate.into()
+    }
+
+    pub fn flags(&self) -> Flags {
+        Flags::from_bits_truncate(self.0.flags as _)
+    }
+
+    /// Frames per second, rounded to the nearest integer.
+    pub fn fps(&self) -> u32 {
+        self.0.fps
+    }
+
+    pub fn is_drop_frame(&self) -> bool {
+        self.flags().contains(Flags::DROP_FRAME)
+    }
+
+    /// Formats the timecode of the frame `frame` frames after the start.
+    pub fn to_string_at(&self, frame: i32) -> String {
+        let mut buf = [0 as c_char; STRING_SIZE];
+
+        unsafe {
+            av_timecode_make_string(&self.0, buf.as_mut_ptr(), frame);
+            string(&buf)
+        }
+    }
+
+    /// Returns the number of frames from the start to the timecode `value`.
+    pub fn frame_at(&self, value: &str) -> Result<i32, Error> {
+        Ok(Self::parse(value, self.rate())?.start() - self.start())
+    }
+
+    /// Returns the binary SMPTE ST 12-1 timecode of the frame `frame` frames
+    /// after the start.
+    pub fn smpte_at(&self, frame: i32) -> u32 {
+        unsafe { av_timecode_get_smpte_from_framenum(&self.0, frame) }
+    }
+}
+
+impl fmt::Display for Timecode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.to_string_at(0))
+    }
+}
+
+impl fmt::Debug for Timecode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Timecode")
+            .field("start", &self.start())
+            .field("rate", &self.rate())
+            .field("flags", &self.flags())
+            .finish()
+    }
+}
+
+impl From<AVTimecode> for Timecode {
+    fn from(value: AVTimecode) -> Self {
+        Timecode(value)
+    }
+}
+
+impl From<Timecode> for AVTimecode {
+    fn from(value: Timecode) -> Self {
+        value.0
+    }
+}
+
+/// Formats a binary SMPTE ST 12-1 timecode. Rates above 30 fps use the field
+/// bit to count frames beyond 30.
+pub fn smpte_to_string(rate: Rational, smpte: u32, prevent_drop_frame: bool) -> String {
+    let mut buf = [0 as c_char; STRING_SIZE];
+
+    unsafe {
+        av_timecode_make_smpte_tc_string2(
+            buf.as_mut_ptr(),
+            rate.into(),
+            smpte,
+            prevent_drop_frame as _,
+            0,
+        );
+        string(&buf)
+    }
+}
+
+/// Formats a 25-bit MPEG GOP timecode, as found in `GOPTimecode` side data.
+pub fn mpeg_to_string(tc25bit: u32) -> String {
+    let mut buf = [0 as c_char; STRING_SIZE];
+
+    unsafe {
+        av_timecode_make_mpeg_tc_string(buf.as_mut_ptr(), tc25bit);
+        string(&buf)
+    }
+}
+
+unsafe fn string(buf: &[c_char; STRING_SIZE]) -> String {
+    CStr::from_ptr(buf.as_ptr()).to_string_lossy().into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let tc = Timecode::parse("01:00:00;00", Rational(30000, 1001)).unwrap();
+        assert!(tc.is_drop_frame());
+        assert_eq!(tc.fps(), 30);
+        assert_eq!(tc.start(), 107_892);
+        assert_eq!(tc.to_string(), "01:00:00;00");
+
+        let tc = Timecode::parse("00:00:01:00", Rational(25, 1)).unwrap();
+        assert!(!tc.is_drop_frame());
+        assert_eq!(tc.start(), 25);
+    }
+
+    #[test]
+    fn drop_frame() {
+        let tc = Timecode::new(Rational(30000, 1001), Flags::DROP_FRAME, 0).unwrap();
+
+        assert_eq!(tc.to_string_at(1799), "00:00:59;29");
+        assert_eq!(tc.to_string_at(1800), "00:01:00;02");
+        assert_eq!(tc.frame_at("00:01:00;02").unwrap(), 1800);
+        assert_eq!(tc.frame_at("00:10:00;00").unwrap(), 17_982);
+    }
+
+    #[test]
+    fn smpte() {
+        let rate = Rational(25, 1);
+        let tc = Timecode::from_components(rate, Flags::empty(), 10, 20, 30, 12).unwrap();
+        let smpte = tc.smpte_at(0);
+
+        assert_eq!(smpte_to_string(rate, smpte, false), "10:20:30:12");
+        assert_eq!(
+            Timecode::from_smpte(rate, smpte).unwrap().start(),
+            tc.start()
+        );
+    }
+}
@@ -0,0 +1,145 @@
+//! Reference-counted user data attached to frames and packets through their
+//! `opaque_ref` buffer.
+//!
+//! Cloning a frame or packet shares the data instead of copying it. Encoders
+//! and decoders opened with `codec::Flags::COPY_OPAQUE` (FFmpeg 6.0+) pass it
+//! from each input to the outputs produced from it, and filter graphs keep it
+//! on the frames they forward.
+
+use std::any::Any;
+use std::mem;
+use std::ptr;
+
+use libc::c_void;
+
+use crate::ffi::*;
+use crate::{Error, Frame};
+
+#[cfg(feature = "codec")]
+use crate::codec::packet::{Mut, Ref};
+#[cfg(feature = "codec")]
+use crate::Packet;
+
+type UserData = Box<dyn Any + Send + Sync>;
+
+// passed as the opaque of the buffers we create, to tell them apart from
+// `opaque_ref` buffers set by FFmpeg or other code
+static MARKER: u8 = 0;
+
+fn marker() -> *mut c_void {
+    &MARKER as *const u8 as *mut c_void
+}
+
+unsafe extern "C" fn free(_opaque: *mut c_void, data: *mut u8) {
+    drop(Box::from_raw(data as *mut UserData));
+}
+
+fn wrap<T: Any + Send + Sync>(value: T) -> Result<*mut AVBufferRef, Error> {
+    let data = Box::into_raw(Box::new(Box::new(value) as UserData));
+
+    unsafe {
+        let buf = av_buffer_create(
+            data as *mut u8,
+            mem::size_of::<UserData>() as _,
+            Some(free),
+            marker(),
+            AV_BUFFER_FLAG_READONLY,
+        );
+
+        if buf.is_null() {
+            drop(Box::from_raw(data));
+
+            return Err(Error::Other {
+                errno: libc::ENOMEM,
+            });
+        }
+
+        Ok(buf)
+    }
+}
+
+unsafe fn get<'a, T: Any>(buf: *const AVBufferRef) -> Option<&'a T> {
+    if buf.is_null() || av_buffer_get_opaque(buf) != marker() {
+        return None;
+    }
+
+    (*((*buf).data as *const UserData)).downcast_ref::<T>()
+}
+
+unsafe fn replace(slot: &mut *mut AVBufferRef, buf: *mut AVBufferRef) {
+    av_buffer_unref(slot);
+    *slot = buf;
+}
+
+impl Frame {
+    /// Returns the user data if it was set through [`Frame::set_user_data`]
+    /// (here or on the packet this frame was decoded from) and is a `T`.
+    pub fn user_data<T: Any>(&self) -> Option<&T> {
+        unsafe { get((*self.as_ptr()).opaque_ref) }
+    }
+
+    /// Attaches user data, replacing any existing `opaque_ref`.
+    pub fn set_user_data<T: Any + Send + Sync>(&mut self, value: T) -> Result<(), Error> {
+        let buf = wrap(value)?;
+
+        unsafe {
+            replace(&mut (*self.as_mut_ptr()).opaque_ref, buf);
+        }
+
+        Ok(())
+    }
+
+    pub fn remove_user_data(&mut self) {
+        unsafe {
+            replace(&mut (*self.as_mut_ptr()).opaque_ref, ptr::null_mut());
+        }
+    }
+}
+
+#[cfg(feature = "codec")]
+impl Packet {
+    /// Returns the user data if it was set through [`Packet::set_user_data`]
+    /// (here or on the frame this packet was encoded from) and is a `T`.
+    pub fn user_data<T: Any>(&self) -> Option<&T> {
+        unsafe { get((*self.as_ptr()).opaque_ref) }
+    }
+
+    /// Attaches user data, replacing any existing `opaque_ref`.
+    pub fn set_user_data<T: Any + Send + Sync>(&mut self, value: T) -> Result<(), Error> {
+        let buf = wrap(value)?;
+
+        unsafe {
+            replace(&mut (*self.as_mut_ptr()).opaque_ref, buf);
+        }
+
+        Ok(())
+    }
+
+    pub fn remove_user_data(&mut self) {
+        unsafe {
+            replace(&mut (*self.as_mut_ptr()).opaque_ref, ptr::null_mut());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn frame() {
+        let value = Arc::new(42u64);
+
+        let mut frame = unsafe { Frame::empty() };
+        assert_eq!(frame.user_data::<Arc<u64>>(), None);
+
+        frame.set_user_data(value.clone()).unwrap();
+        assert_eq!(frame.user_data::<Arc<u64>>(), Some(&value));
+        assert_eq!(frame.user_data::<u64>(), None);
+
+        frame.remove_user_data();
+        assert_eq!(frame.user_data::<Arc<u64>>(), None);
+        assert_eq!(Arc::strong_count(&value), 1);
+    }
+}
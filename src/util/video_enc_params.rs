@@ -0,0 +1,212 @@
+//! Encoding parameters exported by video decoders, such as per-block quantizers.
+//!
+//! Decoders only attach `VIDEO_ENC_PARAMS` side data when asked to, see
+//! `decoder::ExportSideData::VIDEO_ENC_PARAMS`.
+
+use std::iter::FusedIterator;
+use std::mem;
+use std::slice;
+
+use crate::ffi::*;
+use crate::frame::{side_data, Frame};
+use crate::utils;
+
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum Type {
+    None,
+    VP9,
+    H264,
+    MPEG2,
+}
+
+impl From<AVVideoEncParamsType> for Type {
+    fn from(value: AVVideoEncParamsType) -> Self {
+        use AVVideoEncParamsType as AV;
+
+        match value {
+            AV::NONE => Type::None,
+            AV::VP9 => Type::VP9,
+            AV::H264 => Type::H264,
+            AV::MPEG2 => Type::MPEG2,
+
+            _ => unimplemented!(),
+        }
+    }
+}
+
+impl From<Type> for AVVideoEncParamsType {
+    fn from(value: Type) -> Self {
+        use AVVideoEncParamsType as AV;
+
+        match value {
+            Type::None => AV::NONE,
+            Type::VP9 => AV::VP9,
+            Type::H264 => AV::H264,
+            Type::MPEG2 => AV::MPEG2,
+        }
+    }
+}
+
+/// View of `VIDEO_ENC_PARAMS` side data.
+#[derive(Copy, Clone)]
+pub struct VideoEncParams<'a> {
+    header: AVVideoEncParams,
+    data: &'a [u8],
+}
+
+impl<'a> VideoEncParams<'a> {
+    /// Validates the raw side data payload, returning `None` if it is truncated.
+    pub fn new(data: &'a [u8]) -> Option<Self> {
+        let header = unsafe { utils::read_struct::<AVVideoEncParams>(data)? };
+
+        if header.nb_blocks > 0 {
+            let blocks = (header.nb_blocks as usize).checked_mul(header.block_size)?;
+
+            if header.block_size < mem::size_of::<AVVideoBlockParams>()
+                || header.blocks_offset.checked_add(blocks)? > data.len()
+            {
+                return None;
+            }
+        }
+
+        Some(VideoEncParams { header, data })
+    }
+
+    /// The codec-specific meaning of the quantizer values.
+    pub fn kind(&self) -> Type {
+        self.header.type_.into()
+    }
+
+    /// Base quantizer of the frame.
+    pub fn qp(&self) -> i32 {
+        self.header.qp
+    }
+
+    /// Quantizer offsets from [`qp`](Self::qp), indexed by plane and then by
+    /// AC (0) or DC (1) coefficients.
+    pub fn delta_qp(&self) -> [[i32; 2]; 4] {
+        self.header.delta_qp
+    }
+
+    pub fn blocks(&self) -> Blocks<'a> {
+        Blocks {
+            data: self.data,
+            offset: self.header.blocks_offset,
+            stride: self.header.block_size,
+            remaining: self.header.nb_blocks as usize,
+        }
+    }
+}
+
+/// Encoding parameters of a single block.
+#[derive(Copy, Clone)]
+pub struct Block(AVVideoBlockParams);
+
+impl Block {
+    /// Position of the top left corner of the block, in pixels.
+    pub fn position(&self) -> (i32, i32) {
+        (self.0.src_x, self.0.src_y)
+    }
+
+    /// Width and height of the block, in pixels.
+    pub fn size(&self) -> (i32, i32) {
+        (self.0.w, self.0.h)
+    }
+
+    /// Quantizer offset from the frame [`qp`](VideoEncParams::qp).
+    pub fn delta_qp(&self) -> i32 {
+        self.0.delta_qp
+    }
+}
+
+pub struct Blocks<'a> {
+    data: &'a [u8],
+    offset: usize,
+    stride: usize,
+    remaining: usize,
+}
+
+impl<'a> Iterator for Blocks<'a> {
+    type Item = Block;
+
+    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let block = unsafe { utils::read_struct::<AVVideoBlockParams>(&self.data[self.offset..])? };
+        self.offset += self.stride;
+        self.remaining -= 1;
+
+        Some(Block(block))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a> ExactSizeIterator for Blocks<'a> {}
+impl<'a> FusedIterator for Blocks<'a> {}
+
+impl Frame {
+    pub fn video_enc_params(&self) -> Option<VideoEncParams<'_>> {
+        let side_data = self.side_data(side_data::Type::VIDEO_ENC_PARAMS)?;
+
+        // borrow the buffer for the lifetime of the frame rather than the side data handle
+        unsafe {
+            let ptr = side_data.as_ptr();
+            VideoEncParams::new(slice::from_raw_parts((*ptr).data, (*ptr).size as usize))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{format, frame};
+
+    #[test]
+    fn blocks() {
+        let mut frame = frame::Video::new(format::Pixel::YUV420P, 32, 32);
+
+        unsafe {
+            let params = av_video_enc_params_create_side_data(
+                frame.as_mut_ptr(),
+                AVVideoEncParamsType::H264,
+                2,
+            );
+            assert!(!params.is_null());
+            (*params).qp = 26;
+
+            for i in 0..2 {
+                let block = (params as *mut u8)
+                    .add((*params).blocks_offset + i * (*params).block_size)
+                    as *mut AVVideoBlockParams;
+                (*block).src_x = 16 * i as i32;
+                (*block).w = 16;
+                (*block).h = 16;
+                (*block).delta_qp = -(i as i32);
+            }
+        }
+
+        let params = frame.video_enc_params().unwrap();
+        assert_eq!(params.kind(), Type::H264);
+        assert_eq!(params.qp(), 26);
+
+        let blocks = params.blocks();
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(
+            blocks
+                .map(|b| (b.position(), b.size(), b.delta_qp()))
+                .collect::<Vec<_>>(),
+            vec![((0, 0), (16, 16), 0), ((16, 0), (16, 16), -1)]
+        );
+
+        // blocks past the end of the payload are rejected
+        let side_data = frame.side_data(side_data::Type::VIDEO_ENC_PARAMS).unwrap();
+        let data = side_data.data();
+        assert!(VideoEncParams::new(&data[..data.len() - 1]).is_none());
+        assert!(VideoEncParams::new(&data[..8]).is_none());
+    }
+}
@@ -19,3 +19,15 @@ pub unsafe fn optional_str_from_c_ptr<'s>(ptr: *const libc::c_char) -> Option<&'
         Some(str_from_c_ptr(ptr))
     }
 }
+
+/// Reads a `T` from the start of `data`, or returns `None` if `data` is too short.
+///
+/// `T` must be a plain C struct for which any bit pattern is valid.
+#[inline]
+pub unsafe fn read_struct<T: Copy>(data: &[u8]) -> Option<T> {
+    if data.len() < std::mem::size_of::<T>() {
+        None
+    } else {
+        Some(std::ptr::read_unaligned(data.as_ptr() as *const T))
+    }
+}